@@ -0,0 +1,256 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, de::Error};
+
+use crate::living_entity::FacingDirection;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AnimationFinished>();
+
+        app.add_systems(Update, (update_directional_rows, animate_sprites).chain());
+    }
+}
+
+#[derive(Event)]
+pub struct AnimationFinished;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationMode {
+    #[default]
+    Loop,
+    Once,
+    PingPong,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpriteSheet {
+    pub image: String,
+    pub frame_size: Vec2,
+    #[serde(default = "default_frame_count")]
+    pub frame_count: u32,
+    #[serde(default = "default_fps", deserialize_with = "deserialize_fps")]
+    pub fps: f32,
+    #[serde(default)]
+    pub mode: AnimationMode,
+    pub size: Option<Vec2>,
+    pub tint: Option<String>,
+}
+
+impl SpriteSheet {
+    pub fn sprite(&self, assets: &AssetServer) -> (Sprite, SpriteAnimation) {
+        let animation =
+            SpriteAnimation::new(self.frame_size, self.frame_count, self.fps).with_mode(self.mode);
+
+        (
+            Sprite {
+                image: assets.load(&self.image),
+                rect: Some(animation.rect()),
+                custom_size: self.size,
+                color: self
+                    .tint
+                    .as_deref()
+                    .and_then(|tint| Srgba::hex(tint).ok())
+                    .map_or(Color::WHITE, Color::from),
+                ..Default::default()
+            },
+            animation,
+        )
+    }
+}
+
+fn default_frame_count() -> u32 {
+    1
+}
+
+fn default_fps() -> f32 {
+    10.0
+}
+
+fn deserialize_fps<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let fps = f32::deserialize(deserializer)?;
+
+    if fps <= 0.0 {
+        return Err(D::Error::custom(format!("fps must be positive, got {fps}")));
+    }
+
+    Ok(fps)
+}
+
+#[derive(Component, Clone)]
+pub struct SpriteAnimation {
+    pub origin: Vec2,
+    pub frame_size: Vec2,
+    pub frame_count: u32,
+    pub mode: AnimationMode,
+    pub row: u32,
+    pub rest_frame: u32,
+    frame: u32,
+    reversed: bool,
+    playing: bool,
+    frame_timer: Timer,
+}
+
+impl SpriteAnimation {
+    pub fn new(frame_size: Vec2, frame_count: u32, fps: f32) -> Self {
+        let frame_time = if fps > 0.0 { 1.0 / fps } else { 0.0 };
+
+        Self {
+            origin: Vec2::ZERO,
+            frame_size,
+            frame_count,
+            mode: AnimationMode::Loop,
+            row: 0,
+            rest_frame: 0,
+            frame: 0,
+            reversed: false,
+            playing: true,
+            frame_timer: Timer::from_seconds(frame_time, TimerMode::Repeating),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: AnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_origin(mut self, origin: Vec2) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn with_rest_frame(mut self, rest_frame: u32) -> Self {
+        self.rest_frame = rest_frame;
+        self.frame = rest_frame;
+        self
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.reversed = false;
+        self.frame = self.rest_frame;
+        self.frame_timer.reset();
+    }
+
+    pub fn rect(&self) -> Rect {
+        let min = self.origin
+            + Vec2 {
+                x: self.frame as f32 * self.frame_size.x,
+                y: self.row as f32 * self.frame_size.y,
+            };
+
+        Rect::from_corners(min, min + self.frame_size)
+    }
+
+    // Returns true when a `Once` animation reaches its last frame.
+    fn advance(&mut self) -> bool {
+        let last_frame = self.frame_count.saturating_sub(1);
+
+        match self.mode {
+            AnimationMode::Loop => {
+                self.frame = if self.frame >= last_frame {
+                    0
+                } else {
+                    self.frame + 1
+                };
+            }
+            AnimationMode::Once => {
+                if self.frame < last_frame {
+                    self.frame += 1;
+                }
+
+                if self.frame == last_frame {
+                    self.playing = false;
+                    return true;
+                }
+            }
+            AnimationMode::PingPong => {
+                if self.frame >= last_frame {
+                    self.reversed = true;
+                } else if self.frame == 0 {
+                    self.reversed = false;
+                }
+
+                self.frame = if self.reversed {
+                    self.frame.saturating_sub(1)
+                } else {
+                    u32::min(self.frame + 1, last_frame)
+                };
+            }
+        }
+
+        false
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct DirectionalRows {
+    pub down: u32,
+    pub left: u32,
+    pub right: u32,
+    pub up: u32,
+}
+
+fn update_directional_rows(
+    mut animations: Query<
+        (&DirectionalRows, &FacingDirection, &mut SpriteAnimation),
+        Changed<FacingDirection>,
+    >,
+) {
+    for (rows, facing_direction, mut animation) in &mut animations {
+        let direction = facing_direction.0;
+
+        let row = if direction.x.abs() > direction.y.abs() {
+            if direction.x < 0.0 {
+                rows.left
+            } else {
+                rows.right
+            }
+        } else if direction.y > 0.0 {
+            rows.up
+        } else {
+            rows.down
+        };
+
+        if animation.row != row {
+            animation.row = row;
+        }
+    }
+}
+
+fn animate_sprites(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut animations: Query<(Entity, &mut SpriteAnimation, &mut Sprite)>,
+) {
+    let delta = time.delta();
+
+    for (entity, mut animation, mut sprite) in &mut animations {
+        if animation.playing
+            && animation.frame_count > 1
+            && animation.frame_timer.tick(delta).just_finished()
+            && animation.advance()
+        {
+            commands.trigger_targets(AnimationFinished, entity);
+        }
+
+        let rect = animation.rect();
+        if sprite.rect != Some(rect) {
+            sprite.rect = Some(rect);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 
 use crate::living_entity::player::Player;
 
@@ -6,13 +6,19 @@ pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<CursorWorldPosition>();
+
         app.add_systems(Startup, spawn_camera);
+        app.add_systems(PreUpdate, update_cursor_world_position);
         app.add_systems(Update, update_camera_position);
     }
 }
 
 #[derive(Component)]
-struct MainCamera;
+pub struct MainCamera;
+
+#[derive(Resource, Default)]
+pub struct CursorWorldPosition(pub Option<Vec2>);
 
 fn spawn_camera(mut commands: Commands) {
     let mut orthographic_projection = OrthographicProjection::default_2d();
@@ -23,6 +29,23 @@ fn spawn_camera(mut commands: Commands) {
     commands.spawn((Camera2d, projection, MainCamera));
 }
 
+fn update_cursor_world_position(
+    mut cursor_world_position: ResMut<CursorWorldPosition>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    camera: Option<Single<(&Camera, &GlobalTransform), With<MainCamera>>>,
+) {
+    let (Some(window), Some(camera)) = (window, camera) else {
+        cursor_world_position.0 = None;
+        return;
+    };
+
+    let (camera, camera_transform) = *camera;
+
+    cursor_world_position.0 = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok());
+}
+
 fn update_camera_position(
     mut camera: Single<&mut Transform, With<MainCamera>>,
     player: Single<&Transform, (With<Player>, Without<MainCamera>)>,
@@ -1,16 +1,15 @@
 use bevy::prelude::*;
 
 use crate::{
+    camera::CursorWorldPosition,
     interaction::TryInteractingEvent,
     living_entity::{
         EntityController, EntityState,
         player::{Player, PlayerInteractor},
     },
+    mana::Mana,
     skills::SkillTree,
-    spell::{
-        basic_attack::CastBasicAttack, beam::CastBeamSpell, bullets::CastBulletsSpell,
-        fire_ball::CastFireBallSpell,
-    },
+    spell::{CastSpellEvent, definition::Spells},
     ui::ui_dialog::UiNavigator,
 };
 
@@ -25,7 +24,7 @@ impl Plugin for CharacterControllerPlugin {
 
         app.add_systems(
             Update,
-            (handle_entity_controller, handle_inputs)
+            (handle_entity_controller, handle_inputs, toggle_aim_mode)
                 .run_if(in_state(PlayerInputState::CharacterController)),
         );
 
@@ -48,6 +47,16 @@ pub struct InputMap {
     pub spell_1: KeyCode,
     pub spell_2: KeyCode,
     pub spell_3: KeyCode,
+
+    pub toggle_aim_mode: KeyCode,
+    pub aim_mode: AimMode,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AimMode {
+    #[default]
+    Facing,
+    Mouse,
 }
 
 impl Default for InputMap {
@@ -63,6 +72,9 @@ impl Default for InputMap {
             spell_1: KeyCode::KeyJ,
             spell_2: KeyCode::KeyK,
             spell_3: KeyCode::KeyL,
+
+            toggle_aim_mode: KeyCode::KeyM,
+            aim_mode: AimMode::default(),
         }
     }
 }
@@ -97,6 +109,7 @@ pub enum PlayerInputState {
     #[default]
     CharacterController,
     Menu,
+    Dead,
 }
 
 #[derive(Component)]
@@ -135,42 +148,41 @@ fn handle_entity_controller(
 fn handle_inputs(
     input: Res<ButtonInput<KeyCode>>,
     input_map: Res<InputMap>,
+    cursor_world_position: Res<CursorWorldPosition>,
+    spells: Spells,
     mut commands: Commands,
-    player: Single<(Entity, &mut SkillTree), With<Player>>,
+    player: Single<(Entity, &Transform, &mut SkillTree, &mut Mana), With<Player>>,
     player_interactor: Single<Entity, With<PlayerInteractor>>,
 ) {
-    let (player, mut skill_tree) = player.into_inner();
-
-    if input.just_pressed(input_map.attack) {
-        let skill = skill_tree.skill_mut("Slash".into());
-        if skill.unlocked && skill.reload_timer.finished() {
-            commands.trigger_targets(CastBasicAttack, player);
-            skill.reload_timer.reset();
+    let (player, player_transform, mut skill_tree, mut mana) = player.into_inner();
+
+    let aim_direction = match input_map.aim_mode {
+        AimMode::Facing => None,
+        AimMode::Mouse => cursor_world_position
+            .0
+            .map(|cursor| (cursor - player_transform.translation.xy()).normalize_or_zero())
+            .filter(|direction| *direction != Vec2::ZERO),
+    };
+
+    let cast = |spell: &str| CastSpellEvent {
+        direction: aim_direction,
+        ..CastSpellEvent::new(spell)
+    };
+
+    for (key, spell) in [
+        (input_map.attack, "Slash"),
+        (input_map.spell_1, "Bullets"),
+        (input_map.spell_2, "Fire ball"),
+        (input_map.spell_3, "Beam"),
+    ] {
+        if !input.just_pressed(key) || spells.get(spell).is_none() {
+            continue;
         }
-    }
 
-    if input.just_pressed(input_map.spell_1) {
-        let skill = skill_tree.skill_mut("Bullets".into());
-        if skill.unlocked && skill.reload_timer.finished() {
-            commands.trigger_targets(CastBulletsSpell, player);
-            skill.reload_timer.reset();
-        }
-    }
-
-    if input.just_pressed(input_map.spell_2) {
-        let skill = skill_tree.skill_mut("Fire ball".into());
-
-        if skill.unlocked && skill.reload_timer.finished() {
-            commands.trigger_targets(CastFireBallSpell, player);
-            skill.reload_timer.reset();
-        }
-    }
-
-    if input.just_pressed(input_map.spell_3) {
-        let skill = skill_tree.skill_mut("Beam".into());
+        let skill = skill_tree.skill_mut(spell.into());
 
-        if skill.unlocked && skill.reload_timer.finished() {
-            commands.trigger_targets(CastBeamSpell, player);
+        if skill.unlocked && skill.reload_timer.finished() && mana.spend(skill.mana_cost) {
+            commands.trigger_targets(cast(spell), player);
             skill.reload_timer.reset();
         }
     }
@@ -180,6 +192,15 @@ fn handle_inputs(
     }
 }
 
+fn toggle_aim_mode(input: Res<ButtonInput<KeyCode>>, mut input_map: ResMut<InputMap>) {
+    if input.just_pressed(input_map.toggle_aim_mode) {
+        input_map.aim_mode = match input_map.aim_mode {
+            AimMode::Facing => AimMode::Mouse,
+            AimMode::Mouse => AimMode::Facing,
+        };
+    }
+}
+
 fn handle_menu_navigation(
     input: Res<ButtonInput<KeyCode>>,
     input_map: Res<MenuInputMap>,
@@ -2,7 +2,10 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 
-use crate::living_entity::enemy::SpawnEnemyEvent;
+use crate::living_entity::enemy::{
+    SpawnEnemyEvent,
+    archetype::{EnemyArchetype, EnemyArchetypes},
+};
 
 pub struct EnemySpawnerPlugin;
 
@@ -81,6 +84,8 @@ fn spawn_enemy(
     mut commands: Commands,
     time: Res<Time>,
     mut enemy_spawners: Query<(&mut EnemySpawner, &Transform)>,
+    enemy_archetypes: Res<EnemyArchetypes>,
+    archetypes: Res<Assets<EnemyArchetype>>,
 ) {
     let dt = time.delta();
 
@@ -91,7 +96,12 @@ fn spawn_enemy(
 
     for (mut enemy_spawner, transform) in &mut enemy_spawners {
         if enemy_spawner.spawning_rate.tick(dt).just_finished() {
-            commands.trigger(SpawnEnemyEvent(transform.translation.xy() + offset));
+            if let Some(archetype) = pick_archetype(&enemy_archetypes, &archetypes) {
+                commands.trigger(SpawnEnemyEvent {
+                    position: transform.translation.xy() + offset,
+                    archetype,
+                });
+            }
 
             enemy_spawner
                 .spawning_rate
@@ -99,3 +109,26 @@ fn spawn_enemy(
         }
     }
 }
+
+fn pick_archetype(
+    enemy_archetypes: &EnemyArchetypes,
+    archetypes: &Assets<EnemyArchetype>,
+) -> Option<String> {
+    let weighted: Vec<(&String, f32)> = enemy_archetypes
+        .iter()
+        .filter_map(|(id, handle)| Some((id, archetypes.get(handle)?.spawn_weight)))
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect();
+
+    let total_weight: f32 = weighted.iter().map(|(_, weight)| weight).sum();
+    let mut roll = rand::random::<f32>() * total_weight;
+
+    for (id, weight) in &weighted {
+        if roll < *weight {
+            return Some((*id).clone());
+        }
+        roll -= weight;
+    }
+
+    weighted.last().map(|(id, _)| (*id).clone())
+}
@@ -51,6 +51,12 @@ pub struct HeadQuarter {
     state: HeadQuarterState,
 }
 
+impl HeadQuarter {
+    pub fn has_tent(&self) -> bool {
+        matches!(self.state, HeadQuarterState::Tent)
+    }
+}
+
 #[derive(Component)]
 struct HeadQuarterText;
 
@@ -1,18 +1,262 @@
-use bevy::prelude::*;
+use avian2d::prelude::LinearVelocity;
+use bevy::{color::palettes, prelude::*, sprite::Anchor};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::Deserialize;
 
-pub struct HealthPlugin;
+use crate::{
+    living_entity::EntityController,
+    status_effect::{ApplyStatusEffectEvent, StatusEffect},
+};
+
+pub mod damage_number;
+pub mod hit_feedback;
+
+const HEALTH_BAR_WIDTH: f32 = 20.0;
+const HEALTH_BAR_HEIGHT: f32 = 5.0;
+
+#[derive(Default)]
+pub struct HealthPlugin {
+    pub damage_seed: Option<u64>,
+}
 
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>();
+        app.add_event::<DamageTakenEvent>();
+        app.add_event::<HealEvent>();
+
+        app.insert_resource(
+            self.damage_seed
+                .map_or_else(DamageRng::default, DamageRng::from_seed),
+        );
+
         app.add_systems(Startup, load_health_point);
         app.add_observer(add_health_bar);
+        app.add_observer(resolve_damage);
+        app.add_observer(resolve_healing);
+
+        app.add_plugins((damage_number::plugin, hit_feedback::plugin));
 
+        app.add_systems(Update, (regenerate_health, update_invulnerability));
         app.add_systems(PostUpdate, update_health_bar);
     }
 }
 
-#[derive(Component, Clone, Copy, PartialEq, Eq)]
-pub struct Health(pub i32);
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Health {
+    current: i32,
+    max: i32,
+}
+
+impl Health {
+    pub fn new(max: i32) -> Self {
+        let max = max.max(1);
+
+        Self { current: max, max }
+    }
+
+    pub fn current(&self) -> i32 {
+        self.current
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.current as f32 / self.max as f32
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0
+    }
+
+    pub fn damage(&mut self, amount: i32) {
+        self.current = i32::max(self.current - amount.max(0), 0);
+    }
+
+    pub fn heal(&mut self, amount: i32) {
+        self.current = i32::min(self.current + amount.max(0), self.max);
+    }
+
+    pub fn refill(&mut self) {
+        self.current = self.max;
+    }
+
+    pub fn set_max(&mut self, max: i32) {
+        let max = max.max(1);
+
+        self.max = max;
+        self.current = i32::min(self.current, max);
+    }
+}
+
+#[derive(Component)]
+pub struct HealthRegen {
+    pub per_second: f32,
+    accumulated: f32,
+}
+
+impl HealthRegen {
+    pub fn new(per_second: f32) -> Self {
+        Self {
+            per_second,
+            accumulated: 0.0,
+        }
+    }
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct DamageEvent {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: i32,
+    pub damage_type: DamageType,
+    pub knockback: Vec2,
+    pub stagger: f32,
+    pub kind: DamageKind,
+    pub on_hit: Vec<StatusEffect>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DamageKind {
+    Hit,
+    Splash,
+    Tick,
+}
+
+#[derive(Component, Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct CombatStats {
+    pub crit_chance: f32,
+    pub crit_multiplier: f32,
+    pub damage_variance: f32,
+}
+
+impl Default for CombatStats {
+    fn default() -> Self {
+        Self {
+            crit_chance: 0.05,
+            crit_multiplier: 1.5,
+            damage_variance: 0.1,
+        }
+    }
+}
+
+impl CombatStats {
+    pub fn roll(&self, amount: i32, rng: &mut impl Rng) -> (i32, bool) {
+        let variance = if self.damage_variance > 0.0 {
+            rng.random_range(-self.damage_variance..=self.damage_variance)
+        } else {
+            0.0
+        };
+        let critical = rng.random::<f32>() < self.crit_chance;
+        let multiplier = if critical { self.crit_multiplier } else { 1.0 };
+
+        let amount = (amount as f32 * (1.0 + variance) * multiplier).round() as i32;
+
+        (amount, critical)
+    }
+}
+
+#[derive(Component, Default, Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct Resistances {
+    pub physical: f32,
+    pub fire: f32,
+    pub arcane: f32,
+    pub poison: f32,
+}
+
+impl Resistances {
+    pub fn get(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Arcane => self.arcane,
+            DamageType::Poison => self.poison,
+        }
+    }
+
+    pub fn scale(&self, amount: i32, damage_type: DamageType) -> i32 {
+        let multiplier = f32::max(1.0 - self.get(damage_type), 0.0);
+
+        (amount as f32 * multiplier).round() as i32
+    }
+}
+
+#[derive(Resource)]
+pub struct DamageRng(pub StdRng);
+
+impl Default for DamageRng {
+    fn default() -> Self {
+        Self(StdRng::from_os_rng())
+    }
+}
+
+impl DamageRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DamageTakenEvent {
+    pub source: Entity,
+    pub amount: i32,
+    pub damage_type: DamageType,
+    pub kind: DamageKind,
+    pub critical: bool,
+}
+
+#[derive(Component)]
+pub struct Invulnerability {
+    timer: Timer,
+}
+
+impl Invulnerability {
+    pub fn new(duration: f32) -> Self {
+        let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+        timer.tick(timer.duration());
+
+        Self { timer }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.timer.finished()
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.timer.duration().as_secs_f32()
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HealEvent {
+    pub target: Entity,
+    pub amount: i32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Fire,
+    Arcane,
+    Poison,
+}
+
+impl DamageType {
+    pub fn color(&self) -> Color {
+        match self {
+            DamageType::Physical => palettes::basic::WHITE.into(),
+            DamageType::Fire => palettes::css::ORANGE.into(),
+            DamageType::Arcane => palettes::css::MEDIUM_PURPLE.into(),
+            DamageType::Poison => palettes::basic::LIME.into(),
+        }
+    }
+}
 
 #[derive(Resource)]
 struct HealtPointSprite {
@@ -41,19 +285,17 @@ fn add_health_bar(
     let health_point = commands
         .spawn((
             Sprite {
-                custom_size: Some(Vec2 {
-                    x: health.0 as f32 * 5.0 as f32,
-                    y: 5.0,
-                }),
+                custom_size: Some(health_bar_size(health)),
                 image: health_point_sprite.handle.clone(),
                 image_mode: SpriteImageMode::Tiled {
                     tile_x: true,
                     tile_y: true,
                     stretch_value: 1.0,
                 },
+                anchor: Anchor::CenterLeft,
                 ..Default::default()
             },
-            Transform::from_xyz(0.0, 15.0, 0.0),
+            Transform::from_xyz(-HEALTH_BAR_WIDTH / 2.0, 15.0, 0.0),
             HealthBar,
         ))
         .id();
@@ -61,6 +303,13 @@ fn add_health_bar(
     commands.entity(trigger.target()).add_child(health_point);
 }
 
+fn health_bar_size(health: &Health) -> Vec2 {
+    Vec2 {
+        x: HEALTH_BAR_WIDTH * health.fraction(),
+        y: HEALTH_BAR_HEIGHT,
+    }
+}
+
 fn update_health_bar(
     mut entites: Query<(&Children, &Health), Changed<Health>>,
     mut healt_bars: Query<&mut Sprite, With<HealthBar>>,
@@ -68,11 +317,150 @@ fn update_health_bar(
     for (children, health) in &mut entites {
         for child in children {
             if let Ok(mut sprite) = healt_bars.get_mut(*child) {
-                sprite.custom_size = Some(Vec2 {
-                    x: health.0 as f32 * 5.0,
-                    y: 5.0,
-                });
+                sprite.custom_size = Some(health_bar_size(health));
             }
         }
     }
 }
+
+fn resolve_damage(
+    trigger: Trigger<DamageEvent>,
+    mut commands: Commands,
+    mut targets: Query<(
+        &mut Health,
+        Option<&mut Invulnerability>,
+        Option<&Resistances>,
+    )>,
+    mut bodies: Query<(Option<&mut EntityController>, Option<&mut LinearVelocity>)>,
+    combat_stats: Query<&CombatStats>,
+    mut rng: ResMut<DamageRng>,
+) {
+    let damage = trigger.event();
+
+    let Ok((mut health, invulnerability, resistances)) = targets.get_mut(damage.target) else {
+        return;
+    };
+
+    let invulnerability = invulnerability.filter(|_| damage.kind == DamageKind::Hit);
+
+    if invulnerability
+        .as_deref()
+        .is_some_and(Invulnerability::is_active)
+    {
+        return;
+    }
+
+    let (amount, critical) = match combat_stats.get(damage.source) {
+        Ok(combat_stats) if damage.kind != DamageKind::Tick => {
+            combat_stats.roll(damage.amount, &mut rng.0)
+        }
+        _ => (damage.amount, false),
+    };
+
+    let amount = resistances.map_or(amount, |resistances| {
+        resistances.scale(amount, damage.damage_type)
+    });
+
+    if amount > 0
+        && let Some(mut invulnerability) = invulnerability
+    {
+        invulnerability.timer.reset();
+    }
+
+    health.damage(amount);
+
+    for effect in &damage.on_hit {
+        commands.trigger(ApplyStatusEffectEvent {
+            source: damage.source,
+            target: damage.target,
+            effect: *effect,
+        });
+    }
+
+    if amount > 0 {
+        commands.trigger_targets(
+            DamageTakenEvent {
+                source: damage.source,
+                amount,
+                damage_type: damage.damage_type,
+                kind: damage.kind,
+                critical,
+            },
+            damage.target,
+        );
+    }
+
+    if damage.knockback == Vec2::ZERO {
+        return;
+    }
+
+    let Ok((entity_controller, linear_velocity)) = bodies.get_mut(damage.target) else {
+        return;
+    };
+
+    if let Some(mut entity_controller) = entity_controller {
+        if damage.stagger > 0.0 {
+            entity_controller.stagger(damage.knockback, damage.stagger);
+        } else {
+            entity_controller.push(damage.knockback);
+        }
+    } else if let Some(mut linear_velocity) = linear_velocity {
+        linear_velocity.0 += damage.knockback;
+    }
+}
+
+fn resolve_healing(trigger: Trigger<HealEvent>, mut targets: Query<&mut Health>) {
+    if let Ok(mut health) = targets.get_mut(trigger.target) {
+        health.heal(trigger.amount);
+    }
+}
+
+fn regenerate_health(time: Res<Time>, mut entities: Query<(&mut Health, &mut HealthRegen)>) {
+    let delta = time.delta_secs();
+
+    for (mut health, mut regen) in &mut entities {
+        if health.is_dead() || health.current() == health.max() {
+            regen.accumulated = 0.0;
+            continue;
+        }
+
+        regen.accumulated += regen.per_second * delta;
+
+        let amount = regen.accumulated.floor();
+        if amount >= 1.0 {
+            regen.accumulated -= amount;
+            health.heal(amount as i32);
+        }
+    }
+}
+
+fn update_invulnerability(time: Res<Time>, mut entities: Query<&mut Invulnerability>) {
+    let delta = time.delta();
+
+    for mut invulnerability in &mut entities {
+        invulnerability.timer.tick(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_damage_rolls_are_reproducible() {
+        let combat_stats = CombatStats {
+            crit_chance: 0.5,
+            crit_multiplier: 2.0,
+            damage_variance: 0.2,
+        };
+        let mut rng_1 = DamageRng::from_seed(42);
+        let mut rng_2 = DamageRng::from_seed(42);
+
+        for _ in 0..100 {
+            assert_eq!(
+                combat_stats.roll(100, &mut rng_1.0),
+                combat_stats.roll(100, &mut rng_2.0)
+            );
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use super::{DamageTakenEvent, DamageType};
+
+const DAMAGE_NUMBER_FONT_SIZE: f32 = 32.0;
+const DAMAGE_NUMBER_SCALE: f32 = 0.25;
+const CRITICAL_DAMAGE_NUMBER_SCALE: f32 = 0.4;
+const DAMAGE_NUMBER_RISE_SPEED: f32 = 20.0;
+const DAMAGE_NUMBER_LIFETIME: f32 = 0.8;
+const DAMAGE_NUMBER_MERGE_WINDOW: f32 = 0.3;
+
+pub fn plugin(app: &mut App) {
+    app.add_observer(spawn_damage_number);
+
+    app.add_systems(Update, update_damage_numbers);
+}
+
+#[derive(Component)]
+struct DamageNumber {
+    target: Entity,
+    amount: i32,
+    damage_type: DamageType,
+    critical: bool,
+    live_timer: Timer,
+    merge_timer: Timer,
+}
+
+fn spawn_damage_number(
+    trigger: Trigger<DamageTakenEvent>,
+    mut commands: Commands,
+    targets: Query<&GlobalTransform>,
+    mut damage_numbers: Query<(&mut DamageNumber, &mut Text2d, &mut Transform)>,
+) {
+    let damage = trigger.event();
+    let target = trigger.target();
+
+    if damage.amount <= 0 {
+        return;
+    }
+
+    let merged = damage_numbers.iter_mut().find(|(damage_number, ..)| {
+        damage_number.target == target
+            && damage_number.damage_type == damage.damage_type
+            && !damage_number.merge_timer.finished()
+    });
+
+    if let Some((mut damage_number, mut text, mut transform)) = merged {
+        damage_number.amount += damage.amount;
+        damage_number.critical |= damage.critical;
+        damage_number.live_timer.reset();
+        damage_number.merge_timer.reset();
+
+        text.0 = damage_number_text(damage_number.amount, damage_number.critical);
+        transform.scale = Vec3::splat(damage_number_scale(damage_number.critical));
+
+        return;
+    }
+
+    let Ok(target_transform) = targets.get(target) else {
+        return;
+    };
+
+    let jitter = (rand::random::<f32>() - 0.5) * 8.0;
+    let translation = target_transform.translation() + Vec3::new(jitter, 20.0, 10.0);
+
+    commands.spawn((
+        Text2d::new(damage_number_text(damage.amount, damage.critical)),
+        TextFont {
+            font_size: DAMAGE_NUMBER_FONT_SIZE,
+            ..Default::default()
+        },
+        TextColor(damage.damage_type.color()),
+        Transform::from_translation(translation)
+            .with_scale(Vec3::splat(damage_number_scale(damage.critical))),
+        DamageNumber {
+            target,
+            amount: damage.amount,
+            damage_type: damage.damage_type,
+            critical: damage.critical,
+            live_timer: Timer::from_seconds(DAMAGE_NUMBER_LIFETIME, TimerMode::Once),
+            merge_timer: Timer::from_seconds(DAMAGE_NUMBER_MERGE_WINDOW, TimerMode::Once),
+        },
+    ));
+}
+
+fn damage_number_text(amount: i32, critical: bool) -> String {
+    if critical {
+        format!("{amount}!")
+    } else {
+        amount.to_string()
+    }
+}
+
+fn damage_number_scale(critical: bool) -> f32 {
+    if critical {
+        CRITICAL_DAMAGE_NUMBER_SCALE
+    } else {
+        DAMAGE_NUMBER_SCALE
+    }
+}
+
+fn update_damage_numbers(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut damage_numbers: Query<(Entity, &mut DamageNumber, &mut Transform, &mut TextColor)>,
+) {
+    let delta = time.delta();
+
+    for (entity, mut damage_number, mut transform, mut text_color) in &mut damage_numbers {
+        damage_number.live_timer.tick(delta);
+        damage_number.merge_timer.tick(delta);
+
+        if damage_number.live_timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation.y += DAMAGE_NUMBER_RISE_SPEED * delta.as_secs_f32();
+
+        let progress = damage_number.live_timer.fraction();
+        text_color
+            .0
+            .set_alpha(f32::min(2.0 * (1.0 - progress), 1.0));
+    }
+}
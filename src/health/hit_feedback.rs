@@ -0,0 +1,135 @@
+use bevy::{color::palettes, prelude::*};
+
+use super::{DamageKind, DamageTakenEvent, Invulnerability};
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<HitFeedbackSettings>();
+
+    app.add_observer(start_hit_feedback);
+
+    app.add_systems(Update, (update_hit_flashes, update_hit_stop));
+}
+
+#[derive(Resource)]
+pub struct HitFeedbackSettings {
+    pub flash_color: Color,
+    pub critical_flash_color: Color,
+    pub flash_duration: f32,
+    pub flash_interval: f32,
+    pub hit_stop: Option<f32>,
+}
+
+impl Default for HitFeedbackSettings {
+    fn default() -> Self {
+        Self {
+            flash_color: palettes::basic::RED.into(),
+            critical_flash_color: palettes::basic::YELLOW.into(),
+            flash_duration: 0.2,
+            flash_interval: 0.05,
+            hit_stop: None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct HitFlash {
+    original_color: Color,
+    flash_color: Color,
+    live_timer: Timer,
+    blink_timer: Timer,
+}
+
+#[derive(Component)]
+struct HitStop {
+    live_timer: Timer,
+}
+
+fn start_hit_feedback(
+    trigger: Trigger<DamageTakenEvent>,
+    mut commands: Commands,
+    settings: Res<HitFeedbackSettings>,
+    mut targets: Query<(&mut Sprite, Option<&HitFlash>, Option<&Invulnerability>)>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if let Ok((mut sprite, hit_flash, invulnerability)) = targets.get_mut(trigger.target()) {
+        let original_color = hit_flash.map_or(sprite.color, |hit_flash| hit_flash.original_color);
+        let duration = invulnerability.map_or(settings.flash_duration, Invulnerability::duration);
+
+        let flash_color = if trigger.critical {
+            settings.critical_flash_color
+        } else {
+            settings.flash_color
+        };
+
+        sprite.color = flash_color;
+
+        commands.entity(trigger.target()).insert(HitFlash {
+            original_color,
+            flash_color,
+            live_timer: Timer::from_seconds(duration, TimerMode::Once),
+            blink_timer: Timer::from_seconds(settings.flash_interval, TimerMode::Repeating),
+        });
+    }
+
+    if let Some(hit_stop) = settings.hit_stop
+        && trigger.kind == DamageKind::Hit
+    {
+        time.pause();
+
+        commands.spawn(HitStop {
+            live_timer: Timer::from_seconds(hit_stop, TimerMode::Once),
+        });
+    }
+}
+
+fn update_hit_flashes(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut entities: Query<(Entity, &mut HitFlash, &mut Sprite)>,
+) {
+    let delta = time.delta();
+
+    for (entity, mut hit_flash, mut sprite) in &mut entities {
+        hit_flash.live_timer.tick(delta);
+
+        if hit_flash.live_timer.finished() {
+            sprite.color = hit_flash.original_color;
+            commands.entity(entity).remove::<HitFlash>();
+            continue;
+        }
+
+        for _ in 0..hit_flash.blink_timer.tick(delta).times_finished_this_tick() {
+            sprite.color = if sprite.color == hit_flash.flash_color {
+                hit_flash.original_color
+            } else {
+                hit_flash.flash_color
+            };
+        }
+    }
+}
+
+fn update_hit_stop(
+    real_time: Res<Time<Real>>,
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    mut hit_stops: Query<(Entity, &mut HitStop)>,
+) {
+    if hit_stops.is_empty() {
+        return;
+    }
+
+    let delta = real_time.delta();
+    let mut frozen = false;
+
+    for (entity, mut hit_stop) in &mut hit_stops {
+        if hit_stop.live_timer.tick(delta).finished() {
+            commands.entity(entity).despawn();
+        } else {
+            frozen = true;
+        }
+    }
+
+    if !frozen {
+        time.unpause();
+    }
+}
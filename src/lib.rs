@@ -1,3 +1,4 @@
+pub mod animation;
 pub mod camera;
 pub mod character_controller;
 pub mod enemy_spawner;
@@ -5,7 +6,12 @@ pub mod head_quarter;
 pub mod health;
 pub mod interaction;
 pub mod living_entity;
+pub mod mana;
+pub mod navigation;
 pub mod skills;
+pub mod spatial_hash;
 pub mod spell;
+pub mod status_effect;
+pub mod toml_asset;
 pub mod ui;
 pub mod xp;
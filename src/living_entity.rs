@@ -1,30 +1,42 @@
+use std::collections::VecDeque;
+
 use avian2d::prelude::LinearVelocity;
 use bevy::prelude::*;
 
-use crate::living_entity::{
-    character::CharacterPlugin, enemy::EnemyPlugin, npc::NPCPlugin, player::PlayerPlugin,
+use crate::{
+    living_entity::{
+        character::CharacterPlugin, enemy::EnemyPlugin, npc::NPCPlugin, player::PlayerPlugin,
+    },
+    navigation::Navigator,
+    spatial_hash::SpatialLayer,
+    status_effect::StatusEffects,
 };
 
 pub mod character;
 pub mod enemy;
 pub mod npc;
 pub mod player;
+pub mod steering;
+
+const PATH_WAYPOINT_RADIUS: f32 = 4.0;
+const PUSH_DURATION: f32 = 0.15;
 
 pub struct LivingEntityPlugin;
 
 impl Plugin for LivingEntityPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((CharacterPlugin, PlayerPlugin, EnemyPlugin, NPCPlugin));
+        app.add_plugins(steering::plugin);
 
         app.add_systems(Update, controll_entities);
     }
 }
 
 #[derive(Component)]
-#[require(Team)]
+#[require(Team, SpatialLayer = SpatialLayer::LivingEntity)]
 pub struct LivingEntity;
 
-#[derive(Component, Default)]
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Team {
     #[default]
     Neutral,
@@ -36,6 +48,7 @@ pub enum Team {
 pub struct FacingDirection(pub Vec2);
 
 #[derive(Component)]
+#[require(Navigator)]
 pub struct EntityController {
     pub state: EntityState,
     pub stats: EntityStats,
@@ -59,6 +72,10 @@ pub enum EntityState {
     Move {
         direction: Vec2,
     },
+    FollowPath {
+        path: VecDeque<Vec2>,
+        destination: Vec2,
+    },
 }
 
 pub struct EntityStats {
@@ -67,33 +84,142 @@ pub struct EntityStats {
 
 struct EntityInnerStats {
     acceleration_timer: Timer,
+    stagger: Option<Knockback>,
+    push: Option<Knockback>,
+    steering: Vec2,
 }
 
 impl Default for EntityInnerStats {
     fn default() -> Self {
         Self {
             acceleration_timer: Timer::from_seconds(0.2, TimerMode::Once),
+            stagger: None,
+            push: None,
+            steering: Vec2::ZERO,
+        }
+    }
+}
+
+struct Knockback {
+    velocity: Vec2,
+    timer: Timer,
+}
+
+impl Knockback {
+    fn new(velocity: Vec2, previous: Option<&Knockback>, duration: f32) -> Self {
+        Self {
+            velocity: previous.map_or(Vec2::ZERO, Knockback::velocity) + velocity,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
         }
     }
+
+    fn velocity(&self) -> Vec2 {
+        self.velocity * (1.0 - self.timer.fraction())
+    }
+}
+
+impl EntityController {
+    pub fn stagger(&mut self, knockback: Vec2, duration: f32) {
+        self.inner_stats.stagger = Some(Knockback::new(
+            knockback,
+            self.inner_stats.stagger.as_ref(),
+            duration,
+        ));
+    }
+
+    pub fn push(&mut self, knockback: Vec2) {
+        self.inner_stats.push = Some(Knockback::new(
+            knockback,
+            self.inner_stats.push.as_ref(),
+            PUSH_DURATION,
+        ));
+    }
+
+    pub fn is_staggered(&self) -> bool {
+        self.inner_stats.stagger.is_some()
+    }
+
+    pub fn steer(&mut self, steering: Vec2) {
+        self.inner_stats.steering = steering;
+    }
 }
 
 fn controll_entities(
     time: Res<Time>,
     mut entities: Query<(
+        Entity,
         &mut EntityController,
         &mut LinearVelocity,
         Option<&mut FacingDirection>,
     )>,
+    transforms: Query<&Transform>,
+    status_effects: Query<&StatusEffects>,
 ) {
     let dt = time.delta();
 
-    for (mut controller, mut velocity, facing_direction) in &mut entities {
-        match controller.state {
-            EntityState::Idle => {
-                velocity.0 = Vec2::ZERO;
+    for (entity, mut controller, mut velocity, facing_direction) in &mut entities {
+        let status_effects = status_effects.get(entity).ok();
+
+        let mut push = Vec2::ZERO;
+
+        if let Some(knockback) = &mut controller.inner_stats.push {
+            knockback.timer.tick(dt);
+
+            push = knockback.velocity();
+
+            if knockback.timer.finished() {
+                controller.inner_stats.push = None;
+            }
+        }
+
+        if let Some(stagger) = &mut controller.inner_stats.stagger {
+            stagger.timer.tick(dt);
+
+            velocity.0 = stagger.velocity();
+
+            if stagger.timer.finished() {
+                controller.inner_stats.stagger = None;
+            }
+
+            controller.inner_stats.acceleration_timer.reset();
+            continue;
+        }
+
+        if status_effects.is_some_and(StatusEffects::is_stunned) {
+            velocity.0 = push;
+            controller.inner_stats.acceleration_timer.reset();
+            continue;
+        }
+
+        let speed_multiplier = status_effects.map_or(1.0, StatusEffects::speed_multiplier);
+
+        let direction = match &mut controller.state {
+            EntityState::Idle => None,
+            EntityState::Move { direction } => Some(*direction),
+            EntityState::FollowPath { path, .. } => {
+                let position = transforms
+                    .get(entity)
+                    .map(|transform| transform.translation.xy())
+                    .unwrap_or_default();
+
+                while path
+                    .front()
+                    .is_some_and(|waypoint| waypoint.distance(position) <= PATH_WAYPOINT_RADIUS)
+                {
+                    path.pop_front();
+                }
+
+                path.front()
+                    .map(|waypoint| (waypoint - position).normalize_or_zero())
+            }
+        };
+
+        match direction {
+            None => {
+                velocity.0 = push;
                 controller.inner_stats.acceleration_timer.reset();
             }
-            EntityState::Move { direction } => {
+            Some(direction) => {
                 let timer = &mut controller.inner_stats.acceleration_timer;
                 let progress = timer.tick(dt).elapsed_secs();
                 let duration = timer.duration();
@@ -102,7 +228,12 @@ fn controll_entities(
 
                 let acc = easing::EaseFunction::SineOut.sample(progress).unwrap();
 
-                velocity.0 = direction * controller.stats.max_speed * acc;
+                let steered_direction = (direction + controller.inner_stats.steering)
+                    .normalize_or(direction)
+                    .clamp_length_max(direction.length());
+
+                velocity.0 =
+                    steered_direction * controller.stats.max_speed * speed_multiplier * acc + push;
                 if let Some(mut facing_direction) = facing_direction {
                     facing_direction.0 = direction;
                 }
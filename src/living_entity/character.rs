@@ -1,5 +1,16 @@
 use bevy::prelude::*;
 
+use crate::animation::{AnimationMode, DirectionalRows, SpriteAnimation};
+
+const CHARACTER_FRAME_SIZE: Vec2 = Vec2 { x: 16.0, y: 16.0 };
+const CHARACTER_FRAME_COUNT: u32 = 3;
+const CHARACTER_ROWS: DirectionalRows = DirectionalRows {
+    down: 0,
+    left: 1,
+    right: 2,
+    up: 3,
+};
+
 pub struct CharacterPlugin;
 
 impl Plugin for CharacterPlugin {
@@ -18,6 +29,20 @@ pub struct CharacterSprites {
     pub conjurer: Handle<Image>,
 }
 
+pub fn character_animation(column: u32, row: u32) -> (SpriteAnimation, DirectionalRows) {
+    let origin = Vec2 {
+        x: column as f32 * CHARACTER_FRAME_SIZE.x * CHARACTER_FRAME_COUNT as f32,
+        y: row as f32 * CHARACTER_FRAME_SIZE.y * 4.0,
+    };
+
+    let animation = SpriteAnimation::new(CHARACTER_FRAME_SIZE, CHARACTER_FRAME_COUNT, 6.0)
+        .with_mode(AnimationMode::PingPong)
+        .with_origin(origin)
+        .with_rest_frame(1);
+
+    (animation, CHARACTER_ROWS)
+}
+
 pub fn load_sprites(mut commands: Commands, assets: Res<AssetServer>) {
     let character_sprites = CharacterSprites {
         generic: assets.load("characters/01-generic.png"),
@@ -1,25 +1,51 @@
 use std::f32::consts::PI;
 
-use avian2d::prelude::{Collider, CollisionEventsEnabled, LinearVelocity, LockedAxes, RigidBody};
+use ai::{AiDebugOverlay, AiState, EnemyAi};
+use archetype::{EnemyArchetype, EnemyArchetypes, EnemyBehaviour};
+use avian2d::{
+    collision::collider::contact_query,
+    prelude::{
+        Collider, CollisionEventsEnabled, LinearVelocity, LockedAxes, Position, RigidBody, Rotation,
+    },
+};
 use bevy::{color::palettes, prelude::*};
+use threat::ThreatTable;
 
 use crate::{
-    health::Health,
-    living_entity::{EntityController, EntityState, EntityStats, FacingDirection, player::Player},
+    animation::SpriteAnimation,
+    health::{Health, Invulnerability},
+    living_entity::{
+        EntityController, EntityState, EntityStats, FacingDirection, LivingEntity, Team,
+        player::{Player, Respawning},
+    },
+    spatial_hash::{SpatialHash, SpatialLayer},
+    spell::CastSpellEvent,
+    status_effect::StatusEffects,
     xp::SpawnXpEvent,
 };
 
+pub mod ai;
+pub mod archetype;
+pub mod threat;
+
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnEnemyEvent>();
 
-        app.add_systems(Startup, load_enemy_sprites);
+        app.add_plugins((ai::plugin, archetype::plugin, threat::plugin));
 
         app.add_observer(spawn_enemy);
 
-        app.add_systems(Update, (update_enemies, update_enemy_sprites));
+        app.add_systems(
+            Update,
+            (
+                update_chargers.after(ai::update_enemy_ai),
+                update_enemy_attacks,
+                update_enemy_sprites,
+            ),
+        );
         app.add_systems(PostUpdate, despawn_dead_enemies);
 
         app.add_systems(Update, show_closest_enemy);
@@ -27,103 +53,323 @@ impl Plugin for EnemyPlugin {
 }
 
 #[derive(Event)]
-pub struct SpawnEnemyEvent(pub Vec2);
-
-#[derive(Resource)]
-struct EnemySprites {
-    diablo: Handle<Image>,
+pub struct SpawnEnemyEvent {
+    pub position: Vec2,
+    pub archetype: String,
 }
 
 #[derive(Debug, Component, Clone)]
-pub struct Enemy {
-    frame_timer: Timer,
+pub struct Enemy;
+
+#[derive(Component)]
+struct XpDrop(i32);
+
+#[derive(Component)]
+struct EnemyAttacks(Vec<EnemyAttack>);
+
+struct EnemyAttack {
+    spell: String,
+    range: f32,
+    cooldown: Timer,
 }
 
-fn load_enemy_sprites(mut commands: Commands, assets: Res<AssetServer>) {
-    let enemy_sprites = EnemySprites {
-        diablo: assets.load("enemy.png"),
-    };
+#[derive(Component)]
+struct Charge {
+    state: ChargeState,
+    base_speed: f32,
+    cooldown: Timer,
+}
 
-    commands.insert_resource(enemy_sprites);
+enum ChargeState {
+    Ready,
+    Telegraph {
+        direction: Vec2,
+        timer: Timer,
+        indicator: Entity,
+    },
+    Dashing {
+        direction: Vec2,
+        timer: Timer,
+    },
 }
 
 fn spawn_enemy(
     trigger: Trigger<SpawnEnemyEvent>,
     mut commands: Commands,
-    enemy_sprites: Res<EnemySprites>,
+    enemy_archetypes: Res<EnemyArchetypes>,
+    archetypes: Res<Assets<EnemyArchetype>>,
+    assets: Res<AssetServer>,
 ) {
-    let x = trigger.0.x;
-    let y = trigger.0.y;
-
-    commands.spawn((
-        Sprite {
-            image: enemy_sprites.diablo.clone(),
-            rect: Some(Rect::new(0.0, 0.0, 16.0, 16.0)),
-            ..Default::default()
-        },
-        RigidBody::Dynamic,
-        Collider::circle(8.0),
-        CollisionEventsEnabled,
-        LockedAxes::ROTATION_LOCKED,
-        Enemy {
-            frame_timer: Timer::from_seconds(0.15, TimerMode::Repeating),
-        },
-        Transform::from_xyz(x, y, 0.0),
-        Health(10),
-        EntityController {
-            state: EntityState::Idle,
-            stats: EntityStats { max_speed: 40.0 },
-            ..Default::default()
-        },
-    ));
+    let Some(archetype) = enemy_archetypes
+        .get(&trigger.archetype)
+        .and_then(|handle| archetypes.get(handle))
+    else {
+        warn!("Unknown enemy archetype: {}", trigger.archetype);
+        return;
+    };
+
+    let attack_range = archetype
+        .attacks
+        .iter()
+        .map(|attack| attack.range)
+        .fold(0.0, f32::max);
+
+    let attacks = archetype
+        .attacks
+        .iter()
+        .map(|attack| EnemyAttack {
+            spell: attack.spell.clone(),
+            range: attack.range,
+            cooldown: Timer::from_seconds(attack.cooldown, TimerMode::Once),
+        })
+        .collect();
+
+    let enemy = commands
+        .spawn((
+            archetype.sprite.sprite(&assets),
+            RigidBody::Dynamic,
+            Collider::circle(archetype.radius),
+            CollisionEventsEnabled,
+            LockedAxes::ROTATION_LOCKED,
+            Enemy,
+            (
+                archetype.behaviour,
+                EnemyAi::new(trigger.position, attack_range, &archetype.perception),
+                archetype.perception,
+                archetype.ai,
+                ThreatTable::default(),
+                archetype.steering,
+                FacingDirection(Vec2::NEG_Y),
+            ),
+            EnemyAttacks(attacks),
+            XpDrop(archetype.xp),
+            LivingEntity,
+            Team::Enemy,
+            Transform::from_translation(Vec3::from((trigger.position, 0.0))),
+            (
+                Health::new(archetype.health),
+                Invulnerability::new(0.1),
+                archetype.combat_stats,
+                archetype.resistances,
+            ),
+            EntityController {
+                state: EntityState::Idle,
+                stats: EntityStats {
+                    max_speed: archetype.speed,
+                },
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    if let EnemyBehaviour::Charger { cooldown, .. } = archetype.behaviour {
+        commands.entity(enemy).insert(Charge {
+            state: ChargeState::Ready,
+            base_speed: archetype.speed,
+            cooldown: Timer::from_seconds(cooldown, TimerMode::Once),
+        });
+    }
 }
 
-fn update_enemies(
-    mut enemies: Query<(&mut EntityController, &Transform), With<Enemy>>,
-    player: Single<&Transform, With<Player>>,
+fn update_chargers(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut gizmos: Gizmos,
+    ai_debug_overlay: Res<AiDebugOverlay>,
+    mut chargers: Query<(
+        Entity,
+        &mut Charge,
+        &mut EntityController,
+        &Transform,
+        &EnemyBehaviour,
+        &EnemyAi,
+    )>,
+    targets: Query<&Transform>,
+    mut indicators: Query<&mut Sprite, Without<Charge>>,
 ) {
-    for (mut entity_controller, enemy_transform) in &mut enemies {
-        let to_player = (player.translation - enemy_transform.translation).xy();
-
-        let view_dist = 700.0;
+    let delta = time.delta();
 
-        if to_player.length() < view_dist {
-            entity_controller.state = EntityState::Move {
-                direction: to_player.normalize(),
+    for (entity, mut charge, mut entity_controller, transform, behaviour, ai) in &mut chargers {
+        let EnemyBehaviour::Charger {
+            dash_range,
+            dash_speed,
+            dash_duration,
+            telegraph,
+            ..
+        } = *behaviour
+        else {
+            continue;
+        };
+
+        charge.cooldown.tick(delta);
+
+        let position = transform.translation.xy();
+
+        match &mut charge.state {
+            ChargeState::Ready => {
+                let Some(target_transform) = ai
+                    .target
+                    .filter(|_| ai.is_engaged())
+                    .and_then(|target| targets.get(target).ok())
+                else {
+                    continue;
+                };
+
+                let to_target = target_transform.translation.xy() - position;
+
+                if charge.cooldown.finished() && to_target.length() <= dash_range {
+                    let direction = to_target.normalize_or_zero();
+                    let length = dash_speed * dash_duration;
+
+                    let indicator = commands
+                        .spawn((
+                            Sprite::from_color(
+                                palettes::basic::RED.with_alpha(0.0),
+                                Vec2::new(length, 6.0),
+                            ),
+                            Transform::from_translation(Vec3::from((
+                                direction * length / 2.0,
+                                -0.1,
+                            )))
+                            .with_rotation(Quat::from_rotation_z(Vec2::X.angle_to(direction))),
+                        ))
+                        .id();
+                    commands.entity(entity).add_child(indicator);
+
+                    charge.state = ChargeState::Telegraph {
+                        direction,
+                        timer: Timer::from_seconds(telegraph, TimerMode::Once),
+                        indicator,
+                    };
+                }
+            }
+            ChargeState::Telegraph {
+                direction,
+                timer,
+                indicator,
+            } => {
+                entity_controller.state = EntityState::Idle;
+
+                if let Ok(mut sprite) = indicators.get_mut(*indicator) {
+                    sprite.color.set_alpha(0.5 * timer.fraction());
+                }
+
+                if ai_debug_overlay.0 {
+                    gizmos.line_2d(
+                        position,
+                        position + *direction * dash_speed * dash_duration,
+                        palettes::basic::RED,
+                    );
+                }
+
+                if timer.tick(delta).finished() {
+                    commands.entity(*indicator).despawn();
+
+                    entity_controller.stats.max_speed = dash_speed;
+                    charge.state = ChargeState::Dashing {
+                        direction: *direction,
+                        timer: Timer::from_seconds(dash_duration, TimerMode::Once),
+                    };
+                }
+            }
+            ChargeState::Dashing { direction, timer } => {
+                entity_controller.state = EntityState::Move {
+                    direction: *direction,
+                };
+
+                if timer.tick(delta).finished() {
+                    entity_controller.stats.max_speed = charge.base_speed;
+                    charge.cooldown.reset();
+                    charge.state = ChargeState::Ready;
+                }
             }
-        } else {
-            entity_controller.state = EntityState::Idle;
         }
     }
 }
 
-fn update_enemy_sprites(
+type Body<'a> = (&'a Collider, &'a Position, &'a Rotation);
+type Targetable = (With<Health>, Without<Respawning>);
+
+fn edge_distance(
+    (collider_1, position_1, rotation_1): Body,
+    (collider_2, position_2, rotation_2): Body,
+) -> f32 {
+    contact_query::distance(
+        collider_1,
+        *position_1,
+        *rotation_1,
+        collider_2,
+        *position_2,
+        *rotation_2,
+    )
+    .unwrap_or_else(|_| position_1.distance(position_2.0))
+}
+
+fn update_enemy_attacks(
     time: Res<Time>,
-    mut enemies: Query<(&mut Enemy, &mut Sprite, &LinearVelocity)>,
+    mut commands: Commands,
+    mut enemies: Query<(Entity, &mut EnemyAttacks, &EnemyAi)>,
+    bodies: Query<Body, Targetable>,
+    status_effects: Query<&StatusEffects>,
 ) {
     let delta = time.delta();
 
-    for (mut enemy, mut sprite, velocity) in &mut enemies {
-        if velocity.0 == Vec2::ZERO {
-            sprite.rect = Some(Rect::new(0.0, 0.0, 16.0, 16.0));
-            enemy.frame_timer.reset();
-            continue;
+    for (enemy, mut attacks, ai) in &mut enemies {
+        for attack in &mut attacks.0 {
+            attack.cooldown.tick(delta);
         }
 
-        enemy.frame_timer.tick(delta);
-
-        if enemy.frame_timer.just_finished() {
-            let mut rect = sprite.rect.unwrap();
+        if matches!(ai.state, AiState::Flee { .. } | AiState::ReturnToSpawner)
+            || status_effects
+                .get(enemy)
+                .is_ok_and(StatusEffects::is_stunned)
+        {
+            continue;
+        }
 
-            rect.min.x += 16.0;
-            rect.max.x += 16.0;
+        let Some((enemy_body, target_body)) = ai
+            .target
+            .and_then(|target| Some((bodies.get(enemy).ok()?, bodies.get(target).ok()?)))
+        else {
+            continue;
+        };
+
+        let to_target = target_body.1.0 - enemy_body.1.0;
+        let distance = edge_distance(enemy_body, target_body);
+
+        let attack = attacks
+            .0
+            .iter_mut()
+            .find(|attack| attack.cooldown.finished() && distance <= attack.range);
+
+        if let Some(attack) = attack {
+            commands.trigger_targets(
+                CastSpellEvent {
+                    spell: attack.spell.clone(),
+                    caster: None,
+                    direction: Some(to_target.normalize_or_zero()),
+                },
+                enemy,
+            );
+
+            attack.cooldown.reset();
+        }
+    }
+}
 
-            if rect.max.x > 16.0 * 6.0 {
-                rect.min.x = 0.0;
-                rect.max.x = 16.0;
+fn update_enemy_sprites(
+    mut enemies: Query<(&mut SpriteAnimation, &mut Sprite, &LinearVelocity), With<Enemy>>,
+) {
+    for (mut animation, mut sprite, velocity) in &mut enemies {
+        if velocity.0 == Vec2::ZERO {
+            if animation.is_playing() {
+                animation.stop();
             }
+            continue;
+        }
 
-            sprite.rect = Some(rect);
+        if !animation.is_playing() {
+            animation.play();
         }
 
         // flip sprite
@@ -137,15 +383,15 @@ fn update_enemy_sprites(
 
 fn despawn_dead_enemies(
     mut commands: Commands,
-    enemies: Query<(Entity, &Health, &Transform), With<Enemy>>,
+    enemies: Query<(Entity, &Health, &Transform, &XpDrop), With<Enemy>>,
 ) {
-    for (entity, health, transform) in &enemies {
-        if health.0 <= 0 {
+    for (entity, health, transform, xp_drop) in &enemies {
+        if health.is_dead() {
             commands.entity(entity).despawn();
 
             commands.trigger(SpawnXpEvent {
                 location: transform.translation.xy(),
-                amount: 10,
+                amount: xp_drop.0,
             });
         }
     }
@@ -153,29 +399,31 @@ fn despawn_dead_enemies(
 
 fn show_closest_enemy(
     mut gizmos: Gizmos,
-    enemies: Query<&Transform, With<Enemy>>,
+    spatial_hash: Res<SpatialHash>,
+    enemies: Query<(), With<Enemy>>,
     player: Single<(&Transform, &FacingDirection), With<Player>>,
 ) {
-    let enemies = enemies.into_iter().filter(|enemy| {
-        let to_enemy = enemy.translation.xy() - player.0.translation.xy();
+    let player_position = player.0.translation.xy();
 
-        let view_angle = PI / 3.0;
+    let closest_enemy = spatial_hash.nearest(
+        SpatialLayer::LivingEntity,
+        player_position,
+        500.0,
+        1,
+        |entity, position| {
+            let to_enemy = position - player_position;
 
-        let enemy_angle = to_enemy.angle_to(player.1.0).abs();
+            let view_angle = PI / 3.0;
 
-        view_angle > enemy_angle && to_enemy.length() < 500.0
-    });
+            let enemy_angle = to_enemy.angle_to(player.1.0).abs();
 
-    let enemy = enemies.min_by(|enemy_1, enemy_2| {
-        let dist_1 = (enemy_1.translation - player.0.translation).length();
-        let dist_2 = (enemy_2.translation - player.0.translation).length();
-
-        dist_1.partial_cmp(&dist_2).unwrap()
-    });
+            enemies.contains(entity) && view_angle > enemy_angle
+        },
+    );
 
-    if let Some(enemy) = enemy {
+    if let Some((_, enemy_position)) = closest_enemy.first() {
         gizmos.rect_2d(
-            Isometry2d::from_translation(enemy.translation.xy()),
+            Isometry2d::from_translation(*enemy_position),
             Vec2::splat(13.0),
             palettes::basic::FUCHSIA,
         );
@@ -0,0 +1,503 @@
+use std::f32::consts::TAU;
+
+use bevy::{color::palettes, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    health::Health,
+    living_entity::{EntityController, EntityState, FacingDirection, Team},
+    navigation::{NavGrid, Navigator},
+    spatial_hash::{SpatialHash, SpatialLayer},
+    spell::hitbox::FriendlyFire,
+    status_effect::StatusEffects,
+};
+
+use super::{
+    Body, Targetable,
+    archetype::EnemyBehaviour,
+    edge_distance,
+    threat::{Taunt, ThreatSettings, ThreatTable},
+};
+
+const ARRIVAL_DISTANCE: f32 = 4.0;
+const ATTACK_RANGE_HYSTERESIS: f32 = 4.0;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<AiDebugOverlay>();
+
+    app.add_systems(Update, (perceive_targets, update_enemy_ai).chain());
+    app.add_systems(Update, (toggle_ai_debug_overlay, draw_ai_debug_overlay));
+}
+
+#[derive(Component, Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct Perception {
+    pub view_distance: f32,
+    pub view_angle: f32,
+    pub hearing_radius: f32,
+    pub lose_track_after: f32,
+}
+
+impl Default for Perception {
+    fn default() -> Self {
+        Self {
+            view_distance: 250.0,
+            view_angle: 2.0,
+            hearing_radius: 60.0,
+            lose_track_after: 3.0,
+        }
+    }
+}
+
+impl Perception {
+    fn perceives(&self, facing: Vec2, to_target: Vec2) -> bool {
+        let distance = to_target.length();
+
+        if distance <= self.hearing_radius {
+            return true;
+        }
+
+        distance <= self.view_distance && facing.angle_to(to_target).abs() <= self.view_angle / 2.0
+    }
+
+    fn distance_factor(&self, to_target: Vec2) -> f32 {
+        (1.0 - to_target.length() / self.view_distance).max(0.0)
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleBehaviour {
+    #[default]
+    Wander,
+    Patrol,
+}
+
+#[derive(Component, Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct AiSettings {
+    pub idle: IdleBehaviour,
+    pub roam_radius: f32,
+    pub leash_radius: f32,
+    pub flee_below: f32,
+    pub flee_duration: f32,
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Self {
+            idle: IdleBehaviour::Wander,
+            roam_radius: 80.0,
+            leash_radius: 500.0,
+            flee_below: 0.0,
+            flee_duration: 3.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AiState {
+    Idle { timer: Timer },
+    Wander { destination: Vec2 },
+    Patrol { waypoint: usize },
+    Chase,
+    Attack,
+    Flee { timer: Timer },
+    ReturnToSpawner,
+}
+
+impl AiState {
+    fn idle() -> Self {
+        AiState::Idle {
+            timer: Timer::from_seconds(1.0 + rand::random::<f32>() * 2.0, TimerMode::Once),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            AiState::Idle { .. } => palettes::basic::GRAY.into(),
+            AiState::Wander { .. } => palettes::basic::GREEN.into(),
+            AiState::Patrol { .. } => palettes::basic::TEAL.into(),
+            AiState::Chase => palettes::basic::YELLOW.into(),
+            AiState::Attack => palettes::basic::RED.into(),
+            AiState::Flee { .. } => palettes::basic::FUCHSIA.into(),
+            AiState::ReturnToSpawner => palettes::basic::BLUE.into(),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct EnemyAi {
+    pub state: AiState,
+    pub target: Option<Entity>,
+    home: Vec2,
+    last_seen: Vec2,
+    attack_range: f32,
+    lose_track_timer: Timer,
+}
+
+impl EnemyAi {
+    pub fn new(home: Vec2, attack_range: f32, perception: &Perception) -> Self {
+        Self {
+            state: AiState::idle(),
+            target: None,
+            home,
+            last_seen: home,
+            attack_range,
+            lose_track_timer: Timer::from_seconds(perception.lose_track_after, TimerMode::Once),
+        }
+    }
+
+    pub fn is_engaged(&self) -> bool {
+        matches!(self.state, AiState::Chase | AiState::Attack)
+    }
+
+    fn engage(&mut self, target: Entity, position: Vec2) {
+        self.target = Some(target);
+        self.last_seen = position;
+        self.lose_track_timer.reset();
+
+        if !matches!(self.state, AiState::Attack | AiState::Flee { .. }) {
+            self.state = AiState::Chase;
+        }
+    }
+
+    fn give_up(&mut self) {
+        self.target = None;
+        self.state = AiState::ReturnToSpawner;
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct AiDebugOverlay(pub bool);
+
+fn move_towards(
+    navigate: &mut impl FnMut(EntityState, Vec2) -> EntityState,
+    current: EntityState,
+    position: Vec2,
+    destination: Vec2,
+) -> EntityState {
+    if position.distance(destination) <= ARRIVAL_DISTANCE {
+        EntityState::Idle
+    } else {
+        navigate(current, destination)
+    }
+}
+
+fn perceive_targets(
+    time: Res<Time>,
+    friendly_fire: Res<FriendlyFire>,
+    threat_settings: Res<ThreatSettings>,
+    mut enemies: Query<(Entity, &mut EnemyAi, &Transform, &Team, &FacingDirection)>,
+    perceptions: Query<(&Perception, &ThreatTable)>,
+    targets: Query<(Entity, &Transform, &Team, Option<&Taunt>), Targetable>,
+    spatial_hash: Res<SpatialHash>,
+) {
+    let delta = time.delta();
+
+    for (enemy, mut ai, transform, team, facing_direction) in &mut enemies {
+        if matches!(ai.state, AiState::ReturnToSpawner) {
+            continue;
+        }
+
+        let Ok((perception, threat_table)) = perceptions.get(enemy) else {
+            continue;
+        };
+
+        let position = transform.translation.xy();
+        let perception_radius = perception.view_distance.max(perception.hearing_radius);
+
+        let most_threatening_target = spatial_hash
+            .query_radius(SpatialLayer::LivingEntity, position, perception_radius)
+            .filter_map(|(target, _)| targets.get(target).ok())
+            .filter(|(target, _, target_team, _)| {
+                *target != enemy && friendly_fire.can_hit(*team, **target_team)
+            })
+            .filter_map(|(target, target_transform, _, taunt)| {
+                let to_target = target_transform.translation.xy() - position;
+
+                if !perception.perceives(facing_direction.0, to_target) {
+                    return None;
+                }
+
+                let distance_factor = perception.distance_factor(to_target);
+                let mut threat =
+                    threat_table.score(target, distance_factor, taunt, &threat_settings);
+
+                if ai.target == Some(target) {
+                    threat *= threat_settings.current_target_bonus;
+                }
+
+                Some((target, to_target, threat))
+            })
+            .max_by(|(_, _, threat_1), (_, _, threat_2)| threat_1.partial_cmp(threat_2).unwrap())
+            .map(|(target, to_target, _)| (target, to_target));
+
+        match most_threatening_target {
+            Some((target, to_target)) => ai.engage(target, position + to_target),
+            None if ai.target.is_some() && ai.lose_track_timer.tick(delta).finished() => {
+                ai.give_up();
+            }
+            None => {}
+        }
+    }
+}
+
+pub(super) fn update_enemy_ai(
+    time: Res<Time>,
+    mut enemies: Query<(
+        Entity,
+        &mut EnemyAi,
+        &mut EntityController,
+        &mut FacingDirection,
+        &mut Navigator,
+        &Transform,
+    )>,
+    enemies_info: Query<(
+        &AiSettings,
+        &EnemyBehaviour,
+        &Health,
+        Option<&StatusEffects>,
+    )>,
+    bodies: Query<Body, Targetable>,
+    nav_grid: Res<NavGrid>,
+) {
+    let delta = time.delta();
+
+    for (enemy, mut ai, mut entity_controller, mut facing_direction, mut navigator, transform) in
+        &mut enemies
+    {
+        let Ok((settings, behaviour, health, status_effects)) = enemies_info.get(enemy) else {
+            continue;
+        };
+
+        if status_effects.is_some_and(StatusEffects::is_stunned) {
+            entity_controller.state = EntityState::Idle;
+            continue;
+        }
+
+        let position = transform.translation.xy();
+
+        if ai.target.is_some_and(|target| !bodies.contains(target)) {
+            ai.give_up();
+        }
+
+        if ai.is_engaged() {
+            if position.distance(ai.home) > settings.leash_radius {
+                ai.give_up();
+            } else if health.fraction() < settings.flee_below {
+                ai.state = AiState::Flee {
+                    timer: Timer::from_seconds(settings.flee_duration, TimerMode::Once),
+                };
+            }
+        }
+
+        let to_target = ai.last_seen - position;
+        let gap = ai
+            .target
+            .and_then(|target| Some((bodies.get(enemy).ok()?, bodies.get(target).ok()?)))
+            .map_or(to_target.length(), |(enemy_body, target_body)| {
+                edge_distance(enemy_body, target_body)
+            });
+        let target = ai.last_seen;
+        let home = ai.home;
+        let attack_range = ai.attack_range;
+
+        if ai.is_engaged() && to_target != Vec2::ZERO {
+            facing_direction.0 = to_target.normalize();
+        }
+
+        let mut next_state = None;
+
+        let current = std::mem::take(&mut entity_controller.state);
+        let mut navigate = |current, destination| {
+            nav_grid.navigate(&mut navigator, current, position, destination)
+        };
+
+        entity_controller.state = match &mut ai.state {
+            AiState::Idle { timer } => {
+                if timer.tick(delta).finished() {
+                    next_state = Some(match settings.idle {
+                        IdleBehaviour::Wander => AiState::Wander {
+                            destination: home
+                                + Vec2::from_angle(rand::random::<f32>() * TAU)
+                                    * rand::random::<f32>()
+                                    * settings.roam_radius,
+                        },
+                        IdleBehaviour::Patrol => AiState::Patrol { waypoint: 0 },
+                    });
+                }
+
+                EntityState::Idle
+            }
+            AiState::Wander { destination } => {
+                let state = move_towards(&mut navigate, current, position, *destination);
+
+                if matches!(state, EntityState::Idle) {
+                    next_state = Some(AiState::idle());
+                }
+
+                state
+            }
+            AiState::Patrol { waypoint } => {
+                let waypoint_position = patrol_waypoint(home, *waypoint, settings);
+                let state = move_towards(&mut navigate, current, position, waypoint_position);
+
+                if matches!(state, EntityState::Idle) {
+                    *waypoint += 1;
+                }
+
+                state
+            }
+            AiState::Chase => {
+                if gap <= attack_range {
+                    next_state = Some(AiState::Attack);
+                }
+
+                engage_movement(
+                    &mut navigate,
+                    current,
+                    behaviour,
+                    target,
+                    to_target,
+                    gap,
+                    0.0,
+                )
+            }
+            AiState::Attack => {
+                if gap > attack_range + ATTACK_RANGE_HYSTERESIS {
+                    next_state = Some(AiState::Chase);
+                }
+
+                let stop_gap = attack_range * 0.5;
+
+                engage_movement(
+                    &mut navigate,
+                    current,
+                    behaviour,
+                    target,
+                    to_target,
+                    gap,
+                    stop_gap,
+                )
+            }
+            AiState::Flee { timer } => {
+                if timer.tick(delta).finished() {
+                    next_state = Some(AiState::ReturnToSpawner);
+                }
+
+                EntityState::Move {
+                    direction: -to_target.normalize_or_zero(),
+                }
+            }
+            AiState::ReturnToSpawner => {
+                let state = move_towards(&mut navigate, current, position, home);
+
+                if matches!(state, EntityState::Idle) {
+                    next_state = Some(AiState::idle());
+                }
+
+                state
+            }
+        };
+
+        if let Some(next_state) = next_state {
+            if matches!(next_state, AiState::ReturnToSpawner) {
+                ai.target = None;
+            }
+
+            ai.state = next_state;
+        }
+    }
+}
+
+fn patrol_waypoint(home: Vec2, waypoint: usize, settings: &AiSettings) -> Vec2 {
+    const WAYPOINTS: [Vec2; 4] = [Vec2::X, Vec2::Y, Vec2::NEG_X, Vec2::NEG_Y];
+
+    home + WAYPOINTS[waypoint % WAYPOINTS.len()] * settings.roam_radius
+}
+
+fn engage_movement(
+    navigate: &mut impl FnMut(EntityState, Vec2) -> EntityState,
+    current: EntityState,
+    behaviour: &EnemyBehaviour,
+    target: Vec2,
+    to_target: Vec2,
+    gap: f32,
+    stop_gap: f32,
+) -> EntityState {
+    let distance = to_target.length();
+
+    match behaviour {
+        EnemyBehaviour::Ranged { preferred_distance } => {
+            if distance < preferred_distance * 0.8 {
+                EntityState::Move {
+                    direction: -to_target.normalize_or_zero(),
+                }
+            } else if distance > *preferred_distance {
+                navigate(current, target)
+            } else {
+                EntityState::Idle
+            }
+        }
+        EnemyBehaviour::Melee | EnemyBehaviour::Charger { .. } => {
+            if gap <= stop_gap {
+                EntityState::Idle
+            } else {
+                navigate(current, target)
+            }
+        }
+    }
+}
+
+fn toggle_ai_debug_overlay(
+    input: Res<ButtonInput<KeyCode>>,
+    mut ai_debug_overlay: ResMut<AiDebugOverlay>,
+) {
+    if input.just_pressed(KeyCode::F2) {
+        ai_debug_overlay.0 = !ai_debug_overlay.0;
+    }
+}
+
+fn draw_ai_debug_overlay(
+    mut gizmos: Gizmos,
+    ai_debug_overlay: Res<AiDebugOverlay>,
+    enemies: Query<(&EnemyAi, &Transform, &Perception, &FacingDirection)>,
+) {
+    if !ai_debug_overlay.0 {
+        return;
+    }
+
+    for (ai, transform, perception, facing_direction) in &enemies {
+        let position = transform.translation.xy();
+        let color = ai.state.color();
+
+        gizmos.circle_2d(position, 4.0, color);
+        gizmos.circle_2d(position, perception.hearing_radius, color.with_alpha(0.3));
+
+        let facing_angle = Vec2::X.angle_to(facing_direction.0);
+        let cone_points = (0..=8).map(|i| {
+            let angle =
+                facing_angle - perception.view_angle / 2.0 + perception.view_angle * i as f32 / 8.0;
+
+            position + Vec2::from_angle(angle) * perception.view_distance
+        });
+
+        gizmos.linestrip_2d(
+            std::iter::once(position)
+                .chain(cone_points)
+                .chain(std::iter::once(position)),
+            color.with_alpha(0.5),
+        );
+
+        match ai.state {
+            AiState::Chase | AiState::Attack | AiState::Flee { .. } => {
+                gizmos.line_2d(position, ai.last_seen, color);
+            }
+            AiState::ReturnToSpawner => {
+                gizmos.line_2d(position, ai.home, color);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use bevy::{asset::LoadedFolder, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    animation::SpriteSheet,
+    health::{CombatStats, Resistances},
+    living_entity::steering::Steering,
+    toml_asset::TomlAssetPlugin,
+};
+
+use super::ai::{AiSettings, Perception};
+
+pub fn plugin(app: &mut App) {
+    app.add_plugins(TomlAssetPlugin::<EnemyArchetype>::new(&["enemy.toml"]));
+
+    app.add_systems(Startup, load_enemy_archetypes);
+    app.add_systems(Update, index_enemy_archetypes);
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct EnemyArchetype {
+    pub id: String,
+    pub sprite: SpriteSheet,
+    pub health: i32,
+    pub speed: f32,
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+    #[serde(default = "default_xp")]
+    pub xp: i32,
+    #[serde(default)]
+    pub combat_stats: CombatStats,
+    #[serde(default)]
+    pub resistances: Resistances,
+    #[serde(default)]
+    pub behaviour: EnemyBehaviour,
+    #[serde(default)]
+    pub perception: Perception,
+    #[serde(default)]
+    pub ai: AiSettings,
+    #[serde(default)]
+    pub steering: Steering,
+    #[serde(default)]
+    pub attacks: Vec<EnemyAttackDefinition>,
+    #[serde(default = "default_spawn_weight")]
+    pub spawn_weight: f32,
+}
+
+fn default_spawn_weight() -> f32 {
+    1.0
+}
+
+fn default_radius() -> f32 {
+    8.0
+}
+
+fn default_xp() -> i32 {
+    10
+}
+
+#[derive(Component, Deserialize, Debug, Default, Clone, Copy)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EnemyBehaviour {
+    #[default]
+    Melee,
+    Ranged {
+        preferred_distance: f32,
+    },
+    Charger {
+        dash_range: f32,
+        dash_speed: f32,
+        dash_duration: f32,
+        telegraph: f32,
+        cooldown: f32,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnemyAttackDefinition {
+    pub spell: String,
+    pub range: f32,
+    pub cooldown: f32,
+}
+
+#[derive(Resource)]
+pub struct EnemyArchetypes {
+    folder: Handle<LoadedFolder>,
+    archetypes: HashMap<String, Handle<EnemyArchetype>>,
+}
+
+impl EnemyArchetypes {
+    pub fn get(&self, id: &str) -> Option<&Handle<EnemyArchetype>> {
+        self.archetypes.get(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Handle<EnemyArchetype>)> {
+        self.archetypes.iter()
+    }
+}
+
+fn load_enemy_archetypes(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.insert_resource(EnemyArchetypes {
+        folder: assets.load_folder("enemies"),
+        archetypes: HashMap::new(),
+    });
+}
+
+fn index_enemy_archetypes(
+    mut archetype_events: EventReader<AssetEvent<EnemyArchetype>>,
+    mut folder_events: EventReader<AssetEvent<LoadedFolder>>,
+    mut enemy_archetypes: ResMut<EnemyArchetypes>,
+    folders: Res<Assets<LoadedFolder>>,
+    archetypes: Res<Assets<EnemyArchetype>>,
+) {
+    if archetype_events.is_empty() && folder_events.is_empty() {
+        return;
+    }
+
+    archetype_events.clear();
+    folder_events.clear();
+
+    let Some(folder) = folders.get(&enemy_archetypes.folder) else {
+        return;
+    };
+
+    enemy_archetypes.archetypes = folder
+        .handles
+        .iter()
+        .filter_map(|handle| handle.clone().try_typed::<EnemyArchetype>().ok())
+        .filter_map(|handle| Some((archetypes.get(&handle)?.id.clone(), handle)))
+        .collect();
+}
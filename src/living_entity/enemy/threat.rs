@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::health::DamageTakenEvent;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ThreatSettings>();
+
+    app.add_observer(add_damage_threat);
+
+    app.add_systems(Update, decay_threat);
+}
+
+#[derive(Resource)]
+pub struct ThreatSettings {
+    pub distance_weight: f32,
+    pub damage_weight: f32,
+    pub decay_per_second: f32,
+    pub current_target_bonus: f32,
+}
+
+impl Default for ThreatSettings {
+    fn default() -> Self {
+        Self {
+            distance_weight: 10.0,
+            damage_weight: 5.0,
+            decay_per_second: 2.0,
+            current_target_bonus: 1.2,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct Taunt(pub f32);
+
+#[derive(Component, Default)]
+pub struct ThreatTable {
+    threat: HashMap<Entity, f32>,
+}
+
+impl ThreatTable {
+    pub fn get(&self, target: Entity) -> f32 {
+        self.threat.get(&target).copied().unwrap_or_default()
+    }
+
+    pub fn add(&mut self, target: Entity, amount: f32) {
+        *self.threat.entry(target).or_default() += amount;
+    }
+
+    pub fn score(
+        &self,
+        target: Entity,
+        distance_factor: f32,
+        taunt: Option<&Taunt>,
+        settings: &ThreatSettings,
+    ) -> f32 {
+        self.get(target)
+            + distance_factor * settings.distance_weight
+            + taunt.map_or(0.0, |taunt| taunt.0)
+    }
+}
+
+fn add_damage_threat(
+    trigger: Trigger<DamageTakenEvent>,
+    threat_settings: Res<ThreatSettings>,
+    mut threat_tables: Query<&mut ThreatTable>,
+) {
+    if trigger.source == trigger.target() {
+        return;
+    }
+
+    if let Ok(mut threat_table) = threat_tables.get_mut(trigger.target()) {
+        threat_table.add(
+            trigger.source,
+            trigger.amount as f32 * threat_settings.damage_weight,
+        );
+    }
+}
+
+fn decay_threat(
+    time: Res<Time>,
+    threat_settings: Res<ThreatSettings>,
+    mut threat_tables: Query<&mut ThreatTable>,
+    entities: Query<()>,
+) {
+    let decay = threat_settings.decay_per_second * time.delta_secs();
+
+    for mut threat_table in &mut threat_tables {
+        threat_table.threat.retain(|target, threat| {
+            *threat -= decay;
+
+            *threat > 0.0 && entities.contains(*target)
+        });
+    }
+}
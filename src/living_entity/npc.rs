@@ -5,14 +5,18 @@ use bevy::prelude::*;
 use rand::random;
 
 use crate::{
+    animation::SpriteAnimation,
     character_controller::PlayerInputState,
+    health::{CombatStats, Health, HealthRegen, Invulnerability},
     interaction::{Interactable, InteractionEvent},
     living_entity::{
-        EntityController, EntityState, EntityStats, LivingEntity,
+        EntityController, EntityState, EntityStats, FacingDirection, LivingEntity, Team,
         character::{self, CharacterSprites},
+        enemy::threat::Taunt,
         npc::hired_npc::HiredNPC,
         player::{Player, PlayerInteractor},
     },
+    navigation::{NavGrid, Navigator},
     ui::ui_dialog::{
         DialogAction, DialogActionEvent, DialogButton, DialogButtons, DialogNode, DialogTree,
     },
@@ -31,7 +35,7 @@ impl Plugin for NPCPlugin {
 
         app.add_systems(Startup, spawn_initial_npcs.after(character::load_sprites));
         app.add_observer(spawn_npc);
-        app.add_systems(Update, update_free_npcs);
+        app.add_systems(Update, (update_free_npcs, update_npc_sprites));
     }
 }
 
@@ -81,15 +85,9 @@ fn spawn_npc(
     mut commands: Commands,
     sprites: Res<CharacterSprites>,
 ) {
-    let x = rand::random_range(0..5) as f32;
-    let y = rand::random_range(0..=1) as f32;
+    let (animation, directional_rows) =
+        character::character_animation(rand::random_range(0..5), rand::random_range(0..=1));
 
-    let x0 = 16.0 + x * 16.0 * 3.0;
-    let x1 = 16.0 + x * 16.0 * 3.0 + 16.0;
-    let y0 = 0.0 + y * 16.0 * 4.0;
-    let y1 = 0.0 + y * 16.0 * 4.0 + 16.0;
-
-    let rect = Rect::new(x0, y0, x1, y1);
     let x = trigger.0.x;
     let y = trigger.0.y;
 
@@ -98,9 +96,12 @@ fn spawn_npc(
             Name::new(get_random_name()),
             Sprite {
                 image: sprites.generic.clone(),
-                rect: Some(rect),
+                rect: Some(animation.rect()),
                 ..Default::default()
             },
+            animation,
+            directional_rows,
+            FacingDirection(Vec2::NEG_Y),
             Transform::from_xyz(x, y, 0.0),
             NPC,
             FreeNPC,
@@ -181,7 +182,15 @@ fn dialog_action(
                                 .entity(trigger.target())
                                 .remove::<FreeNPC>()
                                 .remove::<DialogTree>()
-                                .insert(HiredNPC);
+                                .insert((
+                                    HiredNPC,
+                                    Team::Ally,
+                                    Health::new(10),
+                                    HealthRegen::new(0.2),
+                                    Invulnerability::new(0.3),
+                                    CombatStats::default(),
+                                    Taunt(15.0),
+                                ));
 
                             next_player_state.set(PlayerInputState::CharacterController);
                             let mut npc = npcs.get_mut(trigger.target()).unwrap();
@@ -205,12 +214,20 @@ fn dialog_action(
 
 fn update_free_npcs(
     time: Res<Time>,
+    nav_grid: Res<NavGrid>,
     mut decision_makers: Query<
-        (&mut DecisionMaker, &GlobalTransform, &mut EntityController),
+        (
+            &mut DecisionMaker,
+            &GlobalTransform,
+            &mut EntityController,
+            &mut Navigator,
+        ),
         With<FreeNPC>,
     >,
 ) {
-    for (mut decision_maker, global_transform, mut entity_controller) in &mut decision_makers {
+    for (mut decision_maker, global_transform, mut entity_controller, mut navigator) in
+        &mut decision_makers
+    {
         match decision_maker.state {
             NPCState::Idle => {
                 if decision_maker.decision_timer.tick(time.delta()).finished() {
@@ -231,9 +248,9 @@ fn update_free_npcs(
                 let to_target = target - pos.xy();
 
                 if to_target.length() > 3.0 {
-                    entity_controller.state = EntityState::Move {
-                        direction: to_target.normalize(),
-                    };
+                    let current = std::mem::take(&mut entity_controller.state);
+                    entity_controller.state =
+                        nav_grid.navigate(&mut navigator, current, pos.xy(), target);
                 } else {
                     entity_controller.state = EntityState::Idle;
                     decision_maker.decision_timer =
@@ -248,3 +265,15 @@ fn update_free_npcs(
         }
     }
 }
+
+fn update_npc_sprites(mut npcs: Query<(&mut SpriteAnimation, &LinearVelocity), With<NPC>>) {
+    for (mut animation, velocity) in &mut npcs {
+        let moving = velocity.0 != Vec2::ZERO;
+
+        if moving && !animation.is_playing() {
+            animation.play();
+        } else if !moving && animation.is_playing() {
+            animation.stop();
+        }
+    }
+}
@@ -2,14 +2,17 @@ use bevy::prelude::*;
 
 use crate::{
     head_quarter::HeadQuarter,
+    health::Health,
     living_entity::{
         EntityController, EntityState,
         npc::{DecisionMaker, NPCState},
     },
+    navigation::{NavGrid, Navigator},
 };
 
 pub fn plugin(app: &mut App) {
     app.add_systems(Update, update_hired_npcs);
+    app.add_systems(PostUpdate, despawn_dead_hired_npcs);
 }
 
 #[derive(Component)]
@@ -17,13 +20,21 @@ pub struct HiredNPC;
 
 fn update_hired_npcs(
     time: Res<Time>,
+    nav_grid: Res<NavGrid>,
     head_quarter: Single<&GlobalTransform, With<HeadQuarter>>,
     mut decision_makers: Query<
-        (&mut DecisionMaker, &GlobalTransform, &mut EntityController),
+        (
+            &mut DecisionMaker,
+            &GlobalTransform,
+            &mut EntityController,
+            &mut Navigator,
+        ),
         With<HiredNPC>,
     >,
 ) {
-    for (mut decision_maker, global_transform, mut entity_controller) in &mut decision_makers {
+    for (mut decision_maker, global_transform, mut entity_controller, mut navigator) in
+        &mut decision_makers
+    {
         match decision_maker.state {
             NPCState::Idle => {
                 if decision_maker.decision_timer.tick(time.delta()).finished() {
@@ -44,9 +55,9 @@ fn update_hired_npcs(
                 let to_target = target - pos.xy();
 
                 if to_target.length() > 3.0 {
-                    entity_controller.state = EntityState::Move {
-                        direction: to_target.normalize(),
-                    };
+                    let current = std::mem::take(&mut entity_controller.state);
+                    entity_controller.state =
+                        nav_grid.navigate(&mut navigator, current, pos.xy(), target);
                 } else {
                     entity_controller.state = EntityState::Idle;
                     decision_maker.decision_timer =
@@ -59,3 +70,14 @@ fn update_hired_npcs(
         }
     }
 }
+
+fn despawn_dead_hired_npcs(
+    mut commands: Commands,
+    hired_npcs: Query<(Entity, &Health), With<HiredNPC>>,
+) {
+    for (entity, health) in &hired_npcs {
+        if health.is_dead() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
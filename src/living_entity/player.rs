@@ -4,10 +4,16 @@ use avian2d::prelude::*;
 use bevy::{color::palettes, prelude::*};
 
 use crate::{
-    character_controller::CharacterController,
+    character_controller::{CharacterController, PlayerInputState},
+    head_quarter::HeadQuarter,
+    health::{CombatStats, Health, HealthRegen, Invulnerability},
     interaction::Interactor,
-    living_entity::{EntityController, EntityState, EntityStats, FacingDirection},
+    living_entity::{
+        EntityController, EntityState, EntityStats, FacingDirection, LivingEntity, Team,
+    },
+    mana::Mana,
     skills::{Skill, SkillTree},
+    status_effect::StatusEffects,
     xp::XpInventory,
 };
 
@@ -15,15 +21,49 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<RespawnSettings>();
+
         app.add_systems(Startup, spawn_player);
+        app.add_systems(
+            PostUpdate,
+            kill_player.run_if(not(in_state(PlayerInputState::Dead))),
+        );
+        app.add_systems(
+            Update,
+            respawn_player.run_if(in_state(PlayerInputState::Dead)),
+        );
         app.add_systems(Update, direction_change);
         app.add_systems(Update, show_player_view);
     }
 }
 
+const PLAYER_HEALTH: i32 = 20;
+const SPAWN_POINT: Vec2 = Vec2::ZERO;
+
 #[derive(Debug, Component, Copy, Clone)]
 pub struct Player;
 
+#[derive(Resource)]
+pub struct RespawnSettings {
+    pub respawn_delay: f32,
+    pub xp_penalty: i32,
+}
+
+impl Default for RespawnSettings {
+    fn default() -> Self {
+        Self {
+            respawn_delay: 3.0,
+            xp_penalty: 200,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Respawning {
+    respawn_timer: Timer,
+    previous_input_state: PlayerInputState,
+}
+
 #[derive(Component, Clone, Copy)]
 pub enum Eye {
     Left,
@@ -104,8 +144,22 @@ fn spawn_player(mut commands: Commands) {
             ..Default::default()
         },
         RigidBody::Kinematic,
+        Collider::rectangle(16.0, 16.0),
+        (
+            Health::new(PLAYER_HEALTH),
+            HealthRegen::new(0.5),
+            Invulnerability::new(0.5),
+            CombatStats {
+                crit_chance: 0.1,
+                crit_multiplier: 2.0,
+                damage_variance: 0.15,
+            },
+        ),
+        Mana::new(100, 5.0),
         facing_direction,
         Player,
+        LivingEntity,
+        Team::Ally,
         {
             let mut xp = XpInventory::new();
             xp.collect_xp(2000);
@@ -167,3 +221,75 @@ fn direction_change(
         }
     }
 }
+
+fn kill_player(
+    mut commands: Commands,
+    player: Single<(Entity, &Health, &mut EntityController), With<Player>>,
+    mut status_effects: Query<&mut StatusEffects>,
+    respawn_settings: Res<RespawnSettings>,
+    player_state: Res<State<PlayerInputState>>,
+    mut next_player_state: ResMut<NextState<PlayerInputState>>,
+) {
+    let (entity, health, mut entity_controller) = player.into_inner();
+
+    if !health.is_dead() {
+        return;
+    }
+
+    entity_controller.state = EntityState::Idle;
+
+    if let Ok(mut status_effects) = status_effects.get_mut(entity) {
+        status_effects.clear();
+    }
+
+    commands.entity(entity).insert((
+        Respawning {
+            respawn_timer: Timer::from_seconds(respawn_settings.respawn_delay, TimerMode::Once),
+            previous_input_state: player_state.get().clone(),
+        },
+        ColliderDisabled,
+        Visibility::Hidden,
+    ));
+
+    next_player_state.set(PlayerInputState::Dead);
+}
+
+fn respawn_player(
+    time: Res<Time>,
+    mut commands: Commands,
+    player: Single<(Entity, &mut Respawning, &mut Transform), With<Player>>,
+    player_resources: Single<(&mut Health, &mut Mana, &mut XpInventory), With<Player>>,
+    head_quarter: Option<Single<(&HeadQuarter, &GlobalTransform)>>,
+    respawn_settings: Res<RespawnSettings>,
+    mut next_player_state: ResMut<NextState<PlayerInputState>>,
+) {
+    let (entity, mut respawning, mut transform) = player.into_inner();
+
+    if !respawning.respawn_timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    let respawn_point = match head_quarter {
+        Some(head_quarter) if head_quarter.0.has_tent() => {
+            head_quarter.1.translation().xy() + Vec2::new(0.0, -24.0)
+        }
+        _ => SPAWN_POINT,
+    };
+
+    let (mut health, mut mana, mut xp_inventory) = player_resources.into_inner();
+
+    transform.translation = Vec3::from((respawn_point, transform.translation.z));
+    health.refill();
+    mana.refill();
+    xp_inventory.lose(respawn_settings.xp_penalty);
+
+    commands
+        .entity(entity)
+        .remove::<(Respawning, ColliderDisabled)>()
+        .insert(Visibility::Inherited);
+
+    next_player_state.set(match respawning.previous_input_state {
+        PlayerInputState::Menu => PlayerInputState::CharacterController,
+        ref previous_input_state => previous_input_state.clone(),
+    });
+}
@@ -0,0 +1,103 @@
+use avian2d::prelude::{LinearVelocity, SpatialQuery, SpatialQueryFilter};
+use bevy::prelude::*;
+use bevy_ecs_tiled::prelude::TiledColliderPolygons;
+use serde::Deserialize;
+
+use crate::spatial_hash::{SpatialHash, SpatialLayer};
+
+use super::EntityController;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Update, update_steering.before(super::controll_entities));
+}
+
+#[derive(Component, Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct Steering {
+    pub separation_radius: f32,
+    pub separation_weight: f32,
+    pub alignment_radius: f32,
+    pub alignment_weight: f32,
+    pub avoidance_distance: f32,
+    pub avoidance_weight: f32,
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Self {
+            separation_radius: 24.0,
+            separation_weight: 1.5,
+            alignment_radius: 48.0,
+            alignment_weight: 0.3,
+            avoidance_distance: 32.0,
+            avoidance_weight: 2.0,
+        }
+    }
+}
+
+fn update_steering(
+    spatial_hash: Res<SpatialHash>,
+    spatial_query: SpatialQuery,
+    mut entities: Query<(
+        Entity,
+        &mut EntityController,
+        &Steering,
+        &Transform,
+        &LinearVelocity,
+    )>,
+    neighbours: Query<&LinearVelocity, With<Steering>>,
+    walls: Query<(), With<TiledColliderPolygons>>,
+) {
+    for (entity, mut entity_controller, steering, transform, linear_velocity) in &mut entities {
+        let position = transform.translation.xy();
+        let search_radius = steering.separation_radius.max(steering.alignment_radius);
+
+        let mut separation = Vec2::ZERO;
+        let mut alignment = Vec2::ZERO;
+
+        for (neighbour, neighbour_position) in
+            spatial_hash.query_radius(SpatialLayer::LivingEntity, position, search_radius)
+        {
+            if neighbour == entity {
+                continue;
+            }
+
+            let Ok(neighbour_velocity) = neighbours.get(neighbour) else {
+                continue;
+            };
+
+            let from_neighbour = position - neighbour_position;
+            let distance = from_neighbour.length();
+
+            if distance < steering.separation_radius {
+                separation += from_neighbour.normalize_or(Vec2::from_angle(entity.index() as f32))
+                    * (1.0 - distance / steering.separation_radius);
+            }
+
+            if distance < steering.alignment_radius {
+                alignment += neighbour_velocity.0.normalize_or_zero();
+            }
+        }
+
+        let mut avoidance = Vec2::ZERO;
+
+        if let Ok(heading) = Dir2::new(linear_velocity.0)
+            && let Some(hit) = spatial_query.cast_ray_predicate(
+                position,
+                heading,
+                steering.avoidance_distance,
+                true,
+                &SpatialQueryFilter::default(),
+                &|collider| walls.contains(collider),
+            )
+        {
+            avoidance = hit.normal * (1.0 - hit.distance / steering.avoidance_distance);
+        }
+
+        entity_controller.steer(
+            separation * steering.separation_weight
+                + alignment.normalize_or_zero() * steering.alignment_weight
+                + avoidance * steering.avoidance_weight,
+        );
+    }
+}
@@ -3,10 +3,12 @@ use bevy::prelude::*;
 use bevy_ecs_tiled::prelude::*;
 
 use combat::{
-    camera::CameraPlugin, character_controller::CharacterControllerPlugin,
-    enemy_spawner::EnemySpawnerPlugin, head_quarter::HeadQuarterPlugin, health::HealthPlugin,
-    interaction::InteractionPlugin, living_entity::LivingEntityPlugin, skills::SkillPlugin,
-    spell::SpellPlugin, ui::UiPlugin, xp::XpPlugin,
+    animation::AnimationPlugin, camera::CameraPlugin,
+    character_controller::CharacterControllerPlugin, enemy_spawner::EnemySpawnerPlugin,
+    head_quarter::HeadQuarterPlugin, health::HealthPlugin, interaction::InteractionPlugin,
+    living_entity::LivingEntityPlugin, mana::ManaPlugin, navigation::NavigationPlugin,
+    skills::SkillPlugin, spatial_hash::SpatialHashPlugin, spell::SpellPlugin,
+    status_effect::StatusEffectPlugin, ui::UiPlugin, xp::XpPlugin,
 };
 
 fn main() {
@@ -28,15 +30,22 @@ fn main() {
         LivingEntityPlugin,
         CharacterControllerPlugin,
         SpellPlugin,
-        HealthPlugin,
+        HealthPlugin {
+            damage_seed: std::env::var("DAMAGE_SEED")
+                .ok()
+                .and_then(|seed| seed.parse().ok()),
+        },
+        ManaPlugin,
         XpPlugin,
         EnemySpawnerPlugin,
         CameraPlugin,
+        AnimationPlugin,
         UiPlugin,
         HeadQuarterPlugin,
         InteractionPlugin,
         SkillPlugin,
     ));
+    app.add_plugins((StatusEffectPlugin, NavigationPlugin, SpatialHashPlugin));
 
     app.add_systems(Startup, setup);
     app.add_systems(Update, toggle_debug_view);
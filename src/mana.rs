@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+pub struct ManaPlugin;
+
+impl Plugin for ManaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, regenerate_mana);
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub struct Mana {
+    current: f32,
+    max: f32,
+    regen_per_second: f32,
+}
+
+impl Mana {
+    pub fn new(max: i32, regen_per_second: f32) -> Self {
+        Self {
+            current: max as f32,
+            max: max as f32,
+            regen_per_second,
+        }
+    }
+
+    pub fn current(&self) -> i32 {
+        self.current as i32
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max as i32
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.current / self.max
+    }
+
+    pub fn spend(&mut self, amount: i32) -> bool {
+        if self.current() >= amount {
+            self.current -= amount as f32;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn restore(&mut self, amount: i32) {
+        self.current = f32::min(self.current + amount as f32, self.max);
+    }
+
+    pub fn refill(&mut self) {
+        self.current = self.max;
+    }
+}
+
+fn regenerate_mana(time: Res<Time>, mut entities: Query<&mut Mana>) {
+    let delta = time.delta_secs();
+
+    for mut mana in &mut entities {
+        if mana.current < mana.max {
+            mana.current = f32::min(mana.current + mana.regen_per_second * delta, mana.max);
+        }
+    }
+}
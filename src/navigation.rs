@@ -0,0 +1,550 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+use avian2d::prelude::{Collider, ColliderAabb, Position, Rotation};
+use bevy::{color::palettes, prelude::*};
+use bevy_ecs_tiled::prelude::TiledColliderPolygons;
+
+use crate::living_entity::{EntityController, EntityState};
+
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavGrid>();
+        app.init_resource::<NavigationDebugOverlay>();
+
+        app.add_systems(
+            Update,
+            (schedule_nav_grid_rebuild, rebuild_nav_grid).chain(),
+        );
+        app.add_systems(Update, tick_navigators);
+        app.add_systems(
+            Update,
+            (
+                toggle_navigation_debug_overlay,
+                draw_navigation_debug_overlay,
+            ),
+        );
+    }
+}
+
+const MAX_EXPANDED_CELLS: usize = 4096;
+const MAX_SNAP_DISTANCE: i32 = 4;
+
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+#[derive(Resource)]
+pub struct NavGrid {
+    pub cell_size: f32,
+    pub clearance: f32,
+    origin: Vec2,
+    size: IVec2,
+    blocked: Vec<bool>,
+    rebuild_timer: Option<Timer>,
+}
+
+impl Default for NavGrid {
+    fn default() -> Self {
+        Self {
+            cell_size: 8.0,
+            clearance: 12.0,
+            origin: Vec2::ZERO,
+            size: IVec2::ZERO,
+            blocked: Vec::new(),
+            rebuild_timer: None,
+        }
+    }
+}
+
+impl NavGrid {
+    pub fn cell(&self, position: Vec2) -> IVec2 {
+        ((position - self.origin) / self.cell_size)
+            .floor()
+            .as_ivec2()
+    }
+
+    pub fn cell_center(&self, cell: IVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * self.cell_size
+    }
+
+    fn index(&self, cell: IVec2) -> Option<usize> {
+        let in_bounds = cell.cmpge(IVec2::ZERO).all() && cell.cmplt(self.size).all();
+
+        in_bounds.then(|| (cell.y * self.size.x + cell.x) as usize)
+    }
+
+    pub fn is_walkable(&self, cell: IVec2) -> bool {
+        self.index(cell).is_none_or(|index| !self.blocked[index])
+    }
+
+    pub fn line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        let start = self.cell(from);
+        let nb_steps = (from.distance(to) / (self.cell_size / 2.0)).ceil() as u32;
+
+        (0..=nb_steps).all(|step| {
+            let t = step as f32 / nb_steps.max(1) as f32;
+            let cell = self.cell(from.lerp(to, t));
+
+            cell == start || self.is_walkable(cell)
+        })
+    }
+
+    pub fn nearest_walkable(&self, position: Vec2) -> Option<IVec2> {
+        let cell = self.cell(position);
+
+        if self.is_walkable(cell) {
+            return Some(cell);
+        }
+
+        (1..=MAX_SNAP_DISTANCE).find_map(|distance| {
+            (-distance..=distance)
+                .flat_map(|y| (-distance..=distance).map(move |x| IVec2::new(x, y)))
+                .filter(|offset| offset.x.abs() == distance || offset.y.abs() == distance)
+                .map(|offset| cell + offset)
+                .filter(|cell| self.is_walkable(*cell))
+                .min_by(|cell_1, cell_2| {
+                    self.cell_center(*cell_1)
+                        .distance_squared(position)
+                        .total_cmp(&self.cell_center(*cell_2).distance_squared(position))
+                })
+        })
+    }
+
+    pub fn find_path(&self, from: Vec2, to: Vec2) -> Option<Vec<Vec2>> {
+        let start = self.cell(from);
+        let goal = self.nearest_walkable(to)?;
+        let to = if goal == self.cell(to) {
+            to
+        } else {
+            self.cell_center(goal)
+        };
+
+        let heuristic = |cell: IVec2| (goal - cell).as_vec2().length();
+
+        let mut open = BinaryHeap::from([OpenCell {
+            cell: start,
+            cost: heuristic(start),
+        }]);
+        let mut came_from = HashMap::<IVec2, IVec2>::new();
+        let mut costs = HashMap::from([(start, 0.0)]);
+        let mut nb_expanded_cells = 0;
+
+        while let Some(OpenCell { cell, .. }) = open.pop() {
+            nb_expanded_cells += 1;
+
+            if nb_expanded_cells > MAX_EXPANDED_CELLS {
+                return None;
+            }
+
+            if cell == goal {
+                return Some(self.build_path(from, to, cell, &came_from));
+            }
+
+            let cost = costs[&cell];
+
+            for offset in NEIGHBOURS {
+                let neighbour = cell + offset;
+
+                let cuts_corner = offset.x != 0
+                    && offset.y != 0
+                    && (!self.is_walkable(cell + IVec2::new(offset.x, 0))
+                        || !self.is_walkable(cell + IVec2::new(0, offset.y)));
+
+                if !self.is_walkable(neighbour) || cuts_corner {
+                    continue;
+                }
+
+                let neighbour_cost = cost + offset.as_vec2().length();
+
+                if costs
+                    .get(&neighbour)
+                    .is_some_and(|known_cost| *known_cost <= neighbour_cost)
+                {
+                    continue;
+                }
+
+                costs.insert(neighbour, neighbour_cost);
+                came_from.insert(neighbour, cell);
+                open.push(OpenCell {
+                    cell: neighbour,
+                    cost: neighbour_cost + heuristic(neighbour),
+                });
+            }
+        }
+
+        None
+    }
+
+    fn build_path(
+        &self,
+        from: Vec2,
+        to: Vec2,
+        goal: IVec2,
+        came_from: &HashMap<IVec2, IVec2>,
+    ) -> Vec<Vec2> {
+        let mut cells = vec![goal];
+
+        while let Some(previous) = came_from.get(cells.last().unwrap()) {
+            cells.push(*previous);
+        }
+
+        cells.pop();
+        cells.reverse();
+
+        let mut waypoints: Vec<Vec2> = cells
+            .into_iter()
+            .map(|cell| self.cell_center(cell))
+            .collect();
+
+        if let Some(last) = waypoints.last_mut() {
+            *last = to;
+        }
+
+        let mut path = Vec::new();
+        let mut anchor = from;
+
+        for i in 0..waypoints.len() {
+            let next = waypoints.get(i + 1).copied();
+
+            if next.is_none_or(|next| !self.line_of_sight(anchor, next)) {
+                path.push(waypoints[i]);
+                anchor = waypoints[i];
+            }
+        }
+
+        path
+    }
+
+    pub fn navigate(
+        &self,
+        navigator: &mut Navigator,
+        current: EntityState,
+        from: Vec2,
+        to: Vec2,
+    ) -> EntityState {
+        let direct = EntityState::Move {
+            direction: (to - from).normalize_or_zero(),
+        };
+
+        if self.line_of_sight(from, to) || self.nearest_walkable(to) == Some(self.cell(from)) {
+            return direct;
+        }
+
+        let can_replan = navigator.replan_timer.finished();
+
+        if let EntityState::FollowPath { path, destination } = current
+            && !path.is_empty()
+            && (destination.distance(to) < self.cell_size || !can_replan)
+        {
+            return EntityState::FollowPath { path, destination };
+        }
+
+        let failed_recently = !navigator.failure_timer.finished()
+            && navigator
+                .failed_destination
+                .is_some_and(|failed_destination| failed_destination.distance(to) < self.cell_size);
+
+        if !can_replan || failed_recently {
+            return direct;
+        }
+
+        navigator.replan_timer.reset();
+
+        match self.find_path(from, to) {
+            Some(path) => {
+                navigator.failed_destination = None;
+
+                EntityState::FollowPath {
+                    path: VecDeque::from(path),
+                    destination: to,
+                }
+            }
+            None => {
+                navigator.failed_destination = Some(to);
+                navigator.failure_timer.reset();
+
+                direct
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Navigator {
+    replan_timer: Timer,
+    failure_timer: Timer,
+    failed_destination: Option<Vec2>,
+}
+
+impl Default for Navigator {
+    fn default() -> Self {
+        let mut replan_timer = Timer::from_seconds(0.5, TimerMode::Once);
+        replan_timer.tick(replan_timer.duration());
+
+        Self {
+            replan_timer,
+            failure_timer: Timer::from_seconds(2.0, TimerMode::Once),
+            failed_destination: None,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+struct OpenCell {
+    cell: IVec2,
+    cost: f32,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Resource, Default)]
+struct NavigationDebugOverlay(bool);
+
+fn schedule_nav_grid_rebuild(
+    mut nav_grid: ResMut<NavGrid>,
+    added_colliders: Query<(), Added<TiledColliderPolygons>>,
+    mut removed_colliders: RemovedComponents<TiledColliderPolygons>,
+) {
+    if !added_colliders.is_empty() || removed_colliders.read().next().is_some() {
+        nav_grid.rebuild_timer = Some(Timer::from_seconds(0.2, TimerMode::Once));
+    }
+}
+
+fn rebuild_nav_grid(
+    time: Res<Time>,
+    mut nav_grid: ResMut<NavGrid>,
+    colliders: Query<(&Collider, &ColliderAabb, &Position, &Rotation), With<TiledColliderPolygons>>,
+) {
+    let Some(rebuild_timer) = &mut nav_grid.rebuild_timer else {
+        return;
+    };
+
+    if !rebuild_timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    nav_grid.rebuild_timer = None;
+
+    let Some((min, max)) = colliders
+        .iter()
+        .map(|(_, aabb, ..)| (aabb.min, aabb.max))
+        .reduce(|(min_1, max_1), (min_2, max_2)| (min_1.min(min_2), max_1.max(max_2)))
+    else {
+        return;
+    };
+
+    let margin = Vec2::splat(nav_grid.cell_size + nav_grid.clearance);
+
+    nav_grid.origin = min - margin;
+    nav_grid.size = ((max - min + 2.0 * margin) / nav_grid.cell_size)
+        .ceil()
+        .as_ivec2();
+    nav_grid.blocked = vec![false; (nav_grid.size.x * nav_grid.size.y) as usize];
+
+    for (collider, aabb, position, rotation) in &colliders {
+        let first_cell = nav_grid.cell(aabb.min - nav_grid.clearance);
+        let last_cell = nav_grid.cell(aabb.max + nav_grid.clearance);
+
+        for y in first_cell.y..=last_cell.y {
+            for x in first_cell.x..=last_cell.x {
+                let cell = IVec2::new(x, y);
+                let distance = collider.distance_to_point(
+                    *position,
+                    *rotation,
+                    nav_grid.cell_center(cell),
+                    true,
+                );
+
+                if distance < nav_grid.clearance
+                    && let Some(index) = nav_grid.index(cell)
+                {
+                    nav_grid.blocked[index] = true;
+                }
+            }
+        }
+    }
+}
+
+fn tick_navigators(time: Res<Time>, mut navigators: Query<&mut Navigator>) {
+    let delta = time.delta();
+
+    for mut navigator in &mut navigators {
+        navigator.replan_timer.tick(delta);
+        navigator.failure_timer.tick(delta);
+    }
+}
+
+fn toggle_navigation_debug_overlay(
+    input: Res<ButtonInput<KeyCode>>,
+    mut navigation_debug_overlay: ResMut<NavigationDebugOverlay>,
+) {
+    if input.just_pressed(KeyCode::F3) {
+        navigation_debug_overlay.0 = !navigation_debug_overlay.0;
+    }
+}
+
+fn draw_navigation_debug_overlay(
+    mut gizmos: Gizmos,
+    navigation_debug_overlay: Res<NavigationDebugOverlay>,
+    nav_grid: Res<NavGrid>,
+    entities: Query<(&EntityController, &Transform)>,
+) {
+    if !navigation_debug_overlay.0 {
+        return;
+    }
+
+    for y in 0..nav_grid.size.y {
+        for x in 0..nav_grid.size.x {
+            let cell = IVec2::new(x, y);
+
+            if !nav_grid.is_walkable(cell) {
+                gizmos.rect_2d(
+                    Isometry2d::from_translation(nav_grid.cell_center(cell)),
+                    Vec2::splat(nav_grid.cell_size),
+                    palettes::basic::MAROON,
+                );
+            }
+        }
+    }
+
+    for (entity_controller, transform) in &entities {
+        if let EntityState::FollowPath { path, .. } = &entity_controller.state {
+            gizmos.linestrip_2d(
+                std::iter::once(transform.translation.xy()).chain(path.iter().copied()),
+                palettes::basic::LIME,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nav_grid(walls: impl IntoIterator<Item = IVec2>) -> NavGrid {
+        let mut nav_grid = NavGrid {
+            size: IVec2::splat(10),
+            blocked: vec![false; 100],
+            ..default()
+        };
+
+        for wall in walls {
+            let index = nav_grid.index(wall).unwrap();
+            nav_grid.blocked[index] = true;
+        }
+
+        nav_grid
+    }
+
+    fn wall() -> impl Iterator<Item = IVec2> {
+        (0..8).map(|y| IVec2::new(5, y))
+    }
+
+    fn enclosure() -> impl Iterator<Item = IVec2> {
+        (4..=6)
+            .flat_map(|y| (6..=8).map(move |x| IVec2::new(x, y)))
+            .filter(|cell| *cell != IVec2::new(7, 5))
+    }
+
+    #[test]
+    fn find_path_goes_around_walls() {
+        let nav_grid = nav_grid(wall());
+        let from = nav_grid.cell_center(IVec2::new(2, 2));
+        let to = nav_grid.cell_center(IVec2::new(8, 2));
+
+        let path = nav_grid.find_path(from, to).unwrap();
+
+        assert_eq!(path.last(), Some(&to));
+        assert!(
+            std::iter::once(from)
+                .chain(path.iter().copied())
+                .all(|waypoint| nav_grid.is_walkable(nav_grid.cell(waypoint)))
+        );
+    }
+
+    #[test]
+    fn find_path_snaps_blocked_goal_to_walkable_cell() {
+        let nav_grid = nav_grid(wall());
+        let from = nav_grid.cell_center(IVec2::new(2, 2));
+        let to = nav_grid.cell_center(IVec2::new(5, 2));
+
+        let path = nav_grid.find_path(from, to).unwrap();
+        let goal = nav_grid.cell(*path.last().unwrap());
+
+        assert!(nav_grid.is_walkable(goal));
+        assert_eq!((goal - IVec2::new(5, 2)).abs().max_element(), 1);
+    }
+
+    #[test]
+    fn find_path_fails_for_unreachable_goal() {
+        let nav_grid = nav_grid(enclosure());
+        let from = nav_grid.cell_center(IVec2::new(1, 5));
+        let to = nav_grid.cell_center(IVec2::new(7, 5));
+
+        assert_eq!(nav_grid.find_path(from, to), None);
+    }
+
+    #[test]
+    fn build_path_smooths_waypoints_with_line_of_sight() {
+        let nav_grid = nav_grid(wall());
+        let from = nav_grid.cell_center(IVec2::new(2, 2));
+        let to = nav_grid.cell_center(IVec2::new(8, 2));
+
+        let path = nav_grid.find_path(from, to).unwrap();
+
+        assert!(path.len() < 8);
+        assert!(
+            std::iter::once(from)
+                .chain(path.iter().copied())
+                .zip(path.iter().copied())
+                .all(|(waypoint_1, waypoint_2)| nav_grid.line_of_sight(waypoint_1, waypoint_2))
+        );
+    }
+
+    #[test]
+    fn build_path_collapses_open_ground_to_destination() {
+        let nav_grid = nav_grid([]);
+        let from = nav_grid.cell_center(IVec2::new(0, 0));
+        let to = nav_grid.cell_center(IVec2::new(9, 3));
+
+        assert_eq!(nav_grid.find_path(from, to), Some(vec![to]));
+    }
+
+    #[test]
+    fn navigate_does_not_retry_failed_search_immediately() {
+        let nav_grid = nav_grid(enclosure());
+        let mut navigator = Navigator::default();
+        let from = nav_grid.cell_center(IVec2::new(1, 5));
+        let to = nav_grid.cell_center(IVec2::new(7, 5));
+
+        nav_grid.navigate(&mut navigator, EntityState::Idle, from, to);
+
+        assert_eq!(navigator.failed_destination, Some(to));
+        assert!(!navigator.replan_timer.finished());
+        assert!(!navigator.failure_timer.finished());
+    }
+}
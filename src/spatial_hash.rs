@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+pub struct SpatialHashPlugin;
+
+impl Plugin for SpatialHashPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialHash>();
+
+        app.add_systems(PreUpdate, update_spatial_hash);
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpatialLayer {
+    LivingEntity,
+    Pickup,
+}
+
+#[derive(Resource)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<(SpatialLayer, IVec2), Vec<(Entity, Vec2)>>,
+}
+
+impl Default for SpatialHash {
+    fn default() -> Self {
+        Self::new(64.0)
+    }
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn insert(&mut self, layer: SpatialLayer, entity: Entity, position: Vec2) {
+        let cell = self.cell(position);
+
+        self.cells
+            .entry((layer, cell))
+            .or_default()
+            .push((entity, position));
+    }
+
+    pub fn query_radius(
+        &self,
+        layer: SpatialLayer,
+        center: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let first_cell = self.cell(center - radius);
+        let last_cell = self.cell(center + radius);
+
+        (first_cell.y..=last_cell.y)
+            .flat_map(move |y| (first_cell.x..=last_cell.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(move |cell| self.cells.get(&(layer, cell)))
+            .flatten()
+            .copied()
+            .filter(move |(_, position)| position.distance_squared(center) <= radius * radius)
+    }
+
+    pub fn nearest(
+        &self,
+        layer: SpatialLayer,
+        center: Vec2,
+        radius: f32,
+        count: usize,
+        filter: impl Fn(Entity, Vec2) -> bool,
+    ) -> Vec<(Entity, Vec2)> {
+        let mut nearest: Vec<(Entity, Vec2)> = self
+            .query_radius(layer, center, radius)
+            .filter(|(entity, position)| filter(*entity, *position))
+            .collect();
+
+        nearest.sort_by(|(_, position_1), (_, position_2)| {
+            position_1
+                .distance_squared(center)
+                .total_cmp(&position_2.distance_squared(center))
+        });
+        nearest.truncate(count);
+
+        nearest
+    }
+}
+
+fn update_spatial_hash(
+    mut spatial_hash: ResMut<SpatialHash>,
+    entities: Query<(Entity, &Transform, &SpatialLayer)>,
+) {
+    spatial_hash.clear();
+
+    for (entity, transform, layer) in &entities {
+        spatial_hash.insert(*layer, entity, transform.translation.xy());
+    }
+}
@@ -1,24 +1,176 @@
 use bevy::prelude::*;
-use bullets::CastBulletsSpell;
+use definition::{SpellDefinition, SpellKind, SpellLibrary};
+use explosion::{Explosion, SpawnExplosionEvent};
 use hitbox::HitboxPlugin;
 
-pub mod basic_attack;
+use crate::{
+    health::{DamageEvent, DamageKind, DamageType},
+    living_entity::FacingDirection,
+    status_effect::StatusEffect,
+};
+
 pub mod beam;
-pub mod bullets;
-pub mod fire_ball;
+pub mod definition;
+pub mod explosion;
 pub mod hitbox;
+pub mod melee;
+pub mod projectile;
+pub mod spawner;
 
 pub struct SpellPlugin;
 
 impl Plugin for SpellPlugin {
     fn build(&self, app: &mut App) {
+        app.add_event::<CastSpellEvent>();
+
         app.add_plugins(HitboxPlugin);
 
         app.add_plugins((
-            basic_attack::plugin,
-            bullets::plugin,
-            fire_ball::plugin,
+            definition::plugin,
+            melee::plugin,
+            projectile::plugin,
+            spawner::plugin,
             beam::plugin,
+            explosion::plugin,
         ));
+
+        app.add_observer(cast_spell);
+    }
+}
+
+#[derive(Event, Clone)]
+pub struct CastSpellEvent {
+    pub spell: String,
+    pub caster: Option<Entity>,
+    pub direction: Option<Vec2>,
+}
+
+impl CastSpellEvent {
+    pub fn new(spell: impl ToString) -> Self {
+        Self {
+            spell: spell.to_string(),
+            caster: None,
+            direction: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SpellCast {
+    pub spell: Handle<SpellDefinition>,
+    pub caster: Entity,
+    pub direction: Vec2,
+}
+
+#[derive(Clone)]
+pub struct SpellPayload {
+    pub caster: Entity,
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub knockback: f32,
+    pub stagger: f32,
+    pub on_hit: Vec<StatusEffect>,
+    pub explosion: Option<Explosion>,
+}
+
+impl SpellPayload {
+    pub fn new(caster: Entity, definition: &SpellDefinition) -> Self {
+        Self {
+            caster,
+            damage: definition.damage,
+            damage_type: definition.damage_type,
+            knockback: definition.knockback,
+            stagger: definition.stagger,
+            on_hit: definition.on_hit.clone(),
+            explosion: definition.explosion,
+        }
+    }
+
+    pub fn hit(&self, commands: &mut Commands, target: Entity, direction: Vec2) {
+        self.deal_damage(commands, target, direction, 1.0, DamageKind::Hit);
+    }
+
+    pub fn splash(&self, commands: &mut Commands, target: Entity, direction: Vec2, scale: f32) {
+        self.deal_damage(commands, target, direction, scale, DamageKind::Splash);
+    }
+
+    pub fn detonate(&self, commands: &mut Commands, center: Vec2) -> bool {
+        let Some(explosion) = self.explosion else {
+            return false;
+        };
+
+        commands.trigger(SpawnExplosionEvent {
+            center,
+            explosion,
+            payload: self.clone(),
+        });
+
+        true
+    }
+
+    fn deal_damage(
+        &self,
+        commands: &mut Commands,
+        target: Entity,
+        direction: Vec2,
+        scale: f32,
+        kind: DamageKind,
+    ) {
+        commands.trigger(DamageEvent {
+            source: self.caster,
+            target,
+            amount: (self.damage as f32 * scale).round() as i32,
+            damage_type: self.damage_type,
+            knockback: direction.normalize_or_zero() * self.knockback * scale,
+            stagger: self.stagger,
+            kind,
+            on_hit: self.on_hit.clone(),
+        });
+    }
+}
+
+fn cast_spell(
+    trigger: Trigger<CastSpellEvent>,
+    mut commands: Commands,
+    spell_library: Res<SpellLibrary>,
+    definitions: Res<Assets<SpellDefinition>>,
+    facing_directions: Query<&FacingDirection>,
+) {
+    let origin = trigger.target();
+    let caster = trigger.caster.unwrap_or(origin);
+
+    let Some(spell) = spell_library.get(&trigger.spell) else {
+        warn!("Unknown spell: {}", trigger.spell);
+        return;
+    };
+
+    let Some(definition) = definitions.get(spell) else {
+        return;
+    };
+
+    let direction = trigger
+        .direction
+        .or_else(|| facing_directions.get(caster).ok().map(|facing| facing.0))
+        .unwrap_or(Vec2::X);
+
+    let spell_cast = SpellCast {
+        spell: spell.clone(),
+        caster,
+        direction,
+    };
+
+    match definition.kind {
+        SpellKind::Melee(_) => {
+            commands.trigger_targets(melee::CastMeleeSpell(spell_cast), origin);
+        }
+        SpellKind::Projectile(_) => {
+            commands.trigger_targets(projectile::CastProjectileSpell(spell_cast), origin);
+        }
+        SpellKind::Beam(_) => {
+            commands.trigger_targets(beam::CastBeamSpell(spell_cast), origin);
+        }
+        SpellKind::Spawner(_) => {
+            commands.trigger_targets(spawner::CastSpawnerSpell(spell_cast), origin);
+        }
     }
 }
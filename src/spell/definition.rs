@@ -0,0 +1,161 @@
+use std::{collections::HashMap, num::NonZeroU32};
+
+use bevy::{asset::LoadedFolder, ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    animation::SpriteSheet,
+    health::DamageType,
+    spell::{
+        explosion::Explosion,
+        hitbox::{HitPolicy, HitboxShape},
+        projectile::{Homing, Split},
+    },
+    status_effect::StatusEffect,
+    toml_asset::TomlAssetPlugin,
+};
+
+pub fn plugin(app: &mut App) {
+    app.add_plugins(TomlAssetPlugin::<SpellDefinition>::new(&["spell.toml"]));
+
+    app.add_systems(Startup, load_spell_library);
+    app.add_systems(Update, index_spell_library);
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct SpellDefinition {
+    pub name: String,
+    pub damage: i32,
+    #[serde(default)]
+    pub damage_type: DamageType,
+    #[serde(default)]
+    pub knockback: f32,
+    #[serde(default = "default_stagger")]
+    pub stagger: f32,
+    #[serde(default)]
+    pub hit_policy: HitPolicy,
+    #[serde(default)]
+    pub on_hit: Vec<StatusEffect>,
+    pub explosion: Option<Explosion>,
+    pub sprite: Option<SpriteSheet>,
+    pub kind: SpellKind,
+}
+
+fn default_stagger() -> f32 {
+    0.2
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SpellKind {
+    Melee(MeleeSpell),
+    Projectile(ProjectileSpell),
+    Beam(BeamSpell),
+    Spawner(SpawnerSpell),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MeleeSpell {
+    pub reach: f32,
+    pub shape: HitboxShape,
+    #[serde(default)]
+    pub offset: Vec2,
+    #[serde(default)]
+    pub rotation: f32,
+    pub lifetime: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectileSpell {
+    pub speed: f32,
+    pub shape: HitboxShape,
+    pub lifetime: f32,
+    #[serde(default = "default_projectile_count")]
+    pub count: NonZeroU32,
+    #[serde(default)]
+    pub interval: f32,
+    #[serde(default)]
+    pub spread: f32,
+    #[serde(default)]
+    pub damping: f32,
+    pub bounce: Option<u32>,
+    pub homing: Option<Homing>,
+    pub split: Option<Split>,
+}
+
+fn default_projectile_count() -> NonZeroU32 {
+    NonZeroU32::MIN
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BeamSpell {
+    pub length: f32,
+    pub width: f32,
+    pub lifetime: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpawnerSpell {
+    pub speed: f32,
+    pub shape: HitboxShape,
+    pub lifetime: f32,
+    pub spawn: String,
+    pub spawn_interval: f32,
+}
+
+#[derive(Resource)]
+pub struct SpellLibrary {
+    folder: Handle<LoadedFolder>,
+    spells: HashMap<String, Handle<SpellDefinition>>,
+}
+
+impl SpellLibrary {
+    pub fn get(&self, spell_name: &str) -> Option<&Handle<SpellDefinition>> {
+        self.spells.get(spell_name)
+    }
+}
+
+#[derive(SystemParam)]
+pub struct Spells<'w> {
+    spell_library: Res<'w, SpellLibrary>,
+    definitions: Res<'w, Assets<SpellDefinition>>,
+}
+
+impl Spells<'_> {
+    pub fn get(&self, spell_name: &str) -> Option<&SpellDefinition> {
+        self.definitions.get(self.spell_library.get(spell_name)?)
+    }
+}
+
+fn load_spell_library(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.insert_resource(SpellLibrary {
+        folder: assets.load_folder("spells"),
+        spells: HashMap::new(),
+    });
+}
+
+fn index_spell_library(
+    mut spell_events: EventReader<AssetEvent<SpellDefinition>>,
+    mut folder_events: EventReader<AssetEvent<LoadedFolder>>,
+    mut spell_library: ResMut<SpellLibrary>,
+    folders: Res<Assets<LoadedFolder>>,
+    definitions: Res<Assets<SpellDefinition>>,
+) {
+    if spell_events.is_empty() && folder_events.is_empty() {
+        return;
+    }
+
+    spell_events.clear();
+    folder_events.clear();
+
+    let Some(folder) = folders.get(&spell_library.folder) else {
+        return;
+    };
+
+    spell_library.spells = folder
+        .handles
+        .iter()
+        .filter_map(|handle| handle.clone().try_typed::<SpellDefinition>().ok())
+        .filter_map(|handle| Some((definitions.get(&handle)?.name.clone(), handle)))
+        .collect();
+}
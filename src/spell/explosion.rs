@@ -0,0 +1,139 @@
+use avian2d::prelude::{Collider, SpatialQuery, SpatialQueryFilter};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{health::Health, living_entity::Team};
+
+use super::{SpellPayload, hitbox::FriendlyFire};
+
+pub fn plugin(app: &mut App) {
+    app.add_event::<SpawnExplosionEvent>();
+
+    app.add_systems(Startup, load_explosion_mesh);
+    app.add_observer(spawn_explosion);
+
+    app.add_systems(Update, update_explosions);
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Explosion {
+    pub radius: f32,
+    #[serde(default)]
+    pub falloff: f32,
+    #[serde(default = "default_explosion_duration")]
+    pub duration: f32,
+}
+
+fn default_explosion_duration() -> f32 {
+    0.3
+}
+
+#[derive(Event, Clone)]
+pub struct SpawnExplosionEvent {
+    pub center: Vec2,
+    pub explosion: Explosion,
+    pub payload: SpellPayload,
+}
+
+#[derive(Resource)]
+struct ExplosionMesh(Handle<Mesh>);
+
+#[derive(Component)]
+struct ExplosionVisual {
+    radius: f32,
+    live_timer: Timer,
+}
+
+fn load_explosion_mesh(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    commands.insert_resource(ExplosionMesh(meshes.add(Circle::new(1.0))));
+}
+
+fn spawn_explosion(
+    trigger: Trigger<SpawnExplosionEvent>,
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    friendly_fire: Res<FriendlyFire>,
+    targets: Query<(&GlobalTransform, &Team, Has<Health>)>,
+    explosion_mesh: Res<ExplosionMesh>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let SpawnExplosionEvent {
+        center,
+        explosion,
+        payload,
+    } = trigger.event();
+
+    let team = targets
+        .get(payload.caster)
+        .map(|(_, team, _)| *team)
+        .unwrap_or_default();
+
+    let hit_targets = spatial_query.shape_intersections(
+        &Collider::circle(explosion.radius),
+        *center,
+        0.0,
+        &SpatialQueryFilter::default(),
+    );
+
+    for target in hit_targets {
+        let Ok((target_transform, target_team, has_health)) = targets.get(target) else {
+            continue;
+        };
+
+        if !has_health || !friendly_fire.can_hit(team, *target_team) {
+            continue;
+        }
+
+        let from_center = target_transform.translation().xy() - center;
+        let distance = f32::min(from_center.length() / explosion.radius, 1.0);
+
+        payload.splash(
+            &mut commands,
+            target,
+            from_center,
+            1.0 - explosion.falloff * distance,
+        );
+    }
+
+    commands.spawn((
+        Mesh2d(explosion_mesh.0.clone()),
+        MeshMaterial2d(materials.add(payload.damage_type.color().with_alpha(0.6))),
+        Transform::from_translation(Vec3::from((*center, 1.0))).with_scale(Vec3::ZERO),
+        ExplosionVisual {
+            radius: explosion.radius,
+            live_timer: Timer::from_seconds(explosion.duration, TimerMode::Once),
+        },
+    ));
+}
+
+fn update_explosions(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut explosions: Query<(
+        Entity,
+        &mut ExplosionVisual,
+        &mut Transform,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let delta = time.delta();
+
+    for (entity, mut explosion, mut transform, material) in &mut explosions {
+        explosion.live_timer.tick(delta);
+
+        if explosion.live_timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = explosion.live_timer.fraction();
+        let scale = explosion.radius * easing::EaseFunction::QuadraticOut.sample(progress).unwrap();
+
+        transform.scale = Vec3::new(scale, scale, 1.0);
+
+        if let Some(material) = materials.get_mut(material) {
+            material.color.set_alpha(0.6 * (1.0 - progress));
+        }
+    }
+}
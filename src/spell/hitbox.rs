@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+
 use avian2d::prelude::*;
 use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, de::Error};
+
+use crate::{interaction::Interactor, living_entity::Team};
 
 pub struct HitboxPlugin;
 
@@ -7,9 +12,11 @@ impl Plugin for HitboxPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnHitboxEvent>();
 
+        app.init_resource::<FriendlyFire>();
+
         app.add_observer(spawn_hitbox);
 
-        app.add_systems(Update, update_hitboxes);
+        app.add_systems(Update, (update_hitboxes, repeat_hits));
     }
 }
 
@@ -20,23 +27,154 @@ pub struct HitEntityEvent(pub Entity);
 
 #[derive(Clone)]
 pub struct HitboxInfo {
-    pub size: Vec2,
+    pub shape: HitboxShape,
+    pub offset: Vec2,
+    pub rotation: f32,
     pub parent: Entity,
+    pub team: Team,
+    pub policy: HitPolicy,
     pub live_timer: Option<Timer>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitboxShape {
+    Rectangle(Vec2),
+    Circle(f32),
+    Capsule { radius: f32, length: f32 },
+    Arc { radius: f32, angle: f32 },
+    Polygon(#[serde(deserialize_with = "deserialize_polygon")] Vec<Vec2>),
+}
+
+impl HitboxShape {
+    const ARC_SEGMENTS: u32 = 8;
+
+    pub fn collider(&self) -> Collider {
+        match self {
+            HitboxShape::Rectangle(size) => Collider::rectangle(size.x, size.y),
+            HitboxShape::Circle(radius) => Collider::circle(*radius),
+            HitboxShape::Capsule { radius, length } => Collider::capsule_endpoints(
+                *radius,
+                Vec2::new(-length / 2.0, 0.0),
+                Vec2::new(length / 2.0, 0.0),
+            ),
+            HitboxShape::Arc { radius, angle } => {
+                let arc_points = (0..=Self::ARC_SEGMENTS).map(|i| {
+                    let t = i as f32 / Self::ARC_SEGMENTS as f32;
+                    Vec2::from_angle(-angle / 2.0 + angle * t) * *radius
+                });
+
+                polygon_collider(std::iter::once(Vec2::ZERO).chain(arc_points).collect())
+            }
+            HitboxShape::Polygon(vertices) => polygon_collider(vertices.clone()),
+        }
+    }
+}
+
+fn deserialize_polygon<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec2>, D::Error> {
+    let vertices = Vec::<Vec2>::deserialize(deserializer)?;
+
+    if vertices.len() < 3 {
+        return Err(D::Error::invalid_length(
+            vertices.len(),
+            &"at least 3 polygon vertices",
+        ));
+    }
+
+    Ok(vertices)
+}
+
+fn polygon_collider(vertices: Vec<Vec2>) -> Collider {
+    let nb_vertices = vertices.len() as u32;
+    let indices = (0..nb_vertices)
+        .map(|i| [i, (i + 1) % nb_vertices])
+        .collect();
+
+    Collider::convex_decomposition(vertices, indices)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitPolicy {
+    #[default]
+    Once,
+    Every(f32),
+    Pierce(u32),
+}
+
 #[derive(Component)]
 struct Hitbox {
+    team: Team,
+    policy: HitPolicy,
+    hit_targets: HashMap<Entity, f32>,
     live_timer: Option<Timer>,
 }
 
+impl Hitbox {
+    fn register_hit(&mut self, target: Entity, now: f32) -> bool {
+        let last_hit = self.hit_targets.get(&target);
+
+        let can_hit = match self.policy {
+            HitPolicy::Once => last_hit.is_none(),
+            HitPolicy::Every(interval) => {
+                last_hit.is_none_or(|last_hit| now - last_hit >= interval)
+            }
+            HitPolicy::Pierce(max_targets) => {
+                last_hit.is_none() && self.hit_targets.len() < max_targets as usize
+            }
+        };
+
+        if !can_hit {
+            return false;
+        }
+
+        self.hit_targets.insert(target, now);
+
+        true
+    }
+}
+
+#[derive(Resource)]
+pub struct FriendlyFire {
+    matrix: [[bool; 3]; 3],
+}
+
+impl Default for FriendlyFire {
+    fn default() -> Self {
+        let mut friendly_fire = Self {
+            matrix: [[true; 3]; 3],
+        };
+
+        friendly_fire.set(Team::Ally, Team::Ally, false);
+        friendly_fire.set(Team::Enemy, Team::Enemy, false);
+
+        friendly_fire
+    }
+}
+
+impl FriendlyFire {
+    pub fn can_hit(&self, attacker: Team, target: Team) -> bool {
+        self.matrix[attacker as usize][target as usize]
+    }
+
+    pub fn set(&mut self, attacker: Team, target: Team, can_hit: bool) {
+        self.matrix[attacker as usize][target as usize] = can_hit;
+    }
+}
+
 fn spawn_hitbox(trigger: Trigger<SpawnHitboxEvent>, mut commands: Commands) {
     let hitbox = commands
         .spawn((
-            Collider::rectangle(trigger.0.size.x, trigger.0.size.y),
+            trigger.0.shape.collider(),
+            Transform::from_translation(Vec3::from((trigger.0.offset, 0.0)))
+                .with_rotation(Quat::from_rotation_z(trigger.0.rotation)),
             Sensor,
             CollisionEventsEnabled,
+            CollidingEntities::default(),
             Hitbox {
+                team: trigger.0.team,
+                policy: trigger.0.policy,
+                hit_targets: HashMap::new(),
                 live_timer: trigger.0.live_timer.clone(),
             },
         ))
@@ -51,11 +189,69 @@ fn spawn_hitbox(trigger: Trigger<SpawnHitboxEvent>, mut commands: Commands) {
 
 fn check_collisions(
     trigger: Trigger<OnCollisionStart>,
+    time: Res<Time>,
+    mut commands: Commands,
+    friendly_fire: Res<FriendlyFire>,
+    mut hitboxes: Query<(&mut Hitbox, &ChildOf)>,
+    interactors: Query<(), With<Interactor>>,
+    teams: Query<&Team>,
+) {
+    if hitboxes.contains(trigger.collider) || interactors.contains(trigger.collider) {
+        return;
+    }
+
+    let (mut hitbox, child_of) = hitboxes.get_mut(trigger.target()).unwrap();
+
+    let target = trigger.body.unwrap_or(trigger.collider);
+    let target_team = teams.get(target).copied().unwrap_or_default();
+
+    if !friendly_fire.can_hit(hitbox.team, target_team) {
+        return;
+    }
+
+    if hitbox.register_hit(target, time.elapsed_secs()) {
+        commands.trigger_targets(HitEntityEvent(target), child_of.parent());
+    }
+}
+
+type Unhittable = Or<(With<Hitbox>, With<Interactor>)>;
+
+fn repeat_hits(
+    time: Res<Time>,
     mut commands: Commands,
-    childs_of: Query<&ChildOf>,
+    friendly_fire: Res<FriendlyFire>,
+    mut hitboxes: Query<(&mut Hitbox, &CollidingEntities, &ChildOf)>,
+    colliders: Query<&ColliderOf>,
+    ignored: Query<(), Unhittable>,
+    teams: Query<&Team>,
 ) {
-    let parent = childs_of.get(trigger.target()).unwrap().parent();
-    commands.trigger_targets(HitEntityEvent(trigger.collider), parent);
+    let now = time.elapsed_secs();
+
+    for (mut hitbox, colliding_entities, child_of) in &mut hitboxes {
+        if !matches!(hitbox.policy, HitPolicy::Every(_)) {
+            continue;
+        }
+
+        for collider in colliding_entities.iter() {
+            if ignored.contains(*collider) {
+                continue;
+            }
+
+            let target = colliders
+                .get(*collider)
+                .map(|collider_of| collider_of.body)
+                .unwrap_or(*collider);
+            let target_team = teams.get(target).copied().unwrap_or_default();
+
+            if !friendly_fire.can_hit(hitbox.team, target_team) {
+                continue;
+            }
+
+            if hitbox.register_hit(target, now) {
+                commands.trigger_targets(HitEntityEvent(target), child_of.parent());
+            }
+        }
+    }
 }
 
 fn update_hitboxes(
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::living_entity::Team;
+
+use super::{
+    SpellCast, SpellPayload,
+    definition::{SpellDefinition, SpellKind},
+    hitbox::{HitEntityEvent, HitboxInfo, SpawnHitboxEvent},
+};
+
+pub fn plugin(app: &mut App) {
+    app.add_event::<CastMeleeSpell>();
+
+    app.add_observer(spawn_melee_attack);
+
+    app.add_systems(Update, update_melee_attacks);
+}
+
+#[derive(Event)]
+pub struct CastMeleeSpell(pub SpellCast);
+
+#[derive(Component)]
+struct MeleeAttack {
+    payload: SpellPayload,
+    direction: Vec2,
+    live_timer: Timer,
+}
+
+fn spawn_melee_attack(
+    trigger: Trigger<CastMeleeSpell>,
+    mut commands: Commands,
+    definitions: Res<Assets<SpellDefinition>>,
+    teams: Query<&Team>,
+    assets: Res<AssetServer>,
+) {
+    let spell_cast = &trigger.0;
+    let Some(definition) = definitions.get(&spell_cast.spell) else {
+        return;
+    };
+
+    let SpellKind::Melee(melee) = &definition.kind else {
+        return;
+    };
+
+    let direction = spell_cast.direction;
+
+    let mut transform = Transform::from_translation(Vec3::from((direction * melee.reach, 0.0)));
+    transform.rotate_z(Vec2::X.angle_to(direction));
+
+    let attack = commands
+        .spawn((
+            MeleeAttack {
+                payload: SpellPayload::new(spell_cast.caster, definition),
+                direction,
+                live_timer: Timer::from_seconds(melee.lifetime, TimerMode::Once),
+            },
+            transform,
+        ))
+        .observe(hit_enemy)
+        .id();
+
+    if let Some(sprite) = &definition.sprite {
+        commands.entity(attack).insert(sprite.sprite(&assets));
+    }
+
+    commands.entity(trigger.target()).add_child(attack);
+
+    commands.trigger(SpawnHitboxEvent(HitboxInfo {
+        shape: melee.shape.clone(),
+        offset: melee.offset,
+        rotation: melee.rotation,
+        parent: attack,
+        team: teams.get(spell_cast.caster).copied().unwrap_or_default(),
+        policy: definition.hit_policy,
+        live_timer: None,
+    }));
+}
+
+fn update_melee_attacks(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut attacks: Query<(Entity, &mut MeleeAttack)>,
+) {
+    let delta = time.delta();
+
+    for (entity, mut attack) in &mut attacks {
+        attack.live_timer.tick(delta);
+
+        if attack.live_timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn hit_enemy(
+    trigger: Trigger<HitEntityEvent>,
+    mut commands: Commands,
+    attacks: Query<&MeleeAttack>,
+) {
+    let attack = attacks.get(trigger.target()).unwrap();
+
+    attack
+        .payload
+        .hit(&mut commands, trigger.0, attack.direction);
+}
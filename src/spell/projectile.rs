@@ -0,0 +1,390 @@
+use std::f32::consts::PI;
+
+use avian2d::prelude::{
+    LinearDamping, LinearVelocity, RigidBody, SpatialQuery, SpatialQueryFilter,
+};
+use bevy::prelude::*;
+use bevy_ecs_tiled::prelude::TiledColliderPolygons;
+use serde::Deserialize;
+
+use crate::{
+    health::Health,
+    living_entity::{Team, player::Respawning},
+    spatial_hash::{SpatialHash, SpatialLayer},
+};
+
+use super::{
+    CastSpellEvent, SpellCast, SpellPayload,
+    definition::{SpellDefinition, SpellKind},
+    hitbox::{FriendlyFire, HitEntityEvent, HitPolicy, HitboxInfo, SpawnHitboxEvent},
+};
+
+pub fn plugin(app: &mut App) {
+    app.add_event::<CastProjectileSpell>();
+
+    app.add_observer(cast_projectile_spell);
+
+    app.add_systems(Update, update_projectile_volleys);
+    app.add_systems(
+        Update,
+        (
+            update_and_despawn_projectiles,
+            home_projectiles,
+            bounce_projectiles,
+        ),
+    );
+}
+
+#[derive(Event)]
+pub struct CastProjectileSpell(pub SpellCast);
+
+#[derive(Component, Deserialize, Debug, Clone, Copy)]
+pub struct Bounce(pub u32);
+
+#[derive(Component, Deserialize, Debug, Clone, Copy)]
+pub struct Homing {
+    pub turn_rate: f32,
+    pub angle: f32,
+    pub range: f32,
+}
+
+#[derive(Component, Deserialize, Debug, Clone)]
+pub struct Split {
+    pub spell: String,
+    pub count: u32,
+    #[serde(default)]
+    pub spread: f32,
+}
+
+#[derive(Component)]
+struct ProjectileVolley {
+    spell_cast: SpellCast,
+    origin: Entity,
+    position: Vec3,
+    nb_projectiles_left: u32,
+    projectile_timer: Timer,
+}
+
+#[derive(Component)]
+struct Projectile {
+    payload: SpellPayload,
+    pierce_left: u32,
+    live_timer: Timer,
+}
+
+fn cast_projectile_spell(
+    trigger: Trigger<CastProjectileSpell>,
+    mut commands: Commands,
+    definitions: Res<Assets<SpellDefinition>>,
+    origins: Query<&GlobalTransform>,
+) {
+    let spell_cast = &trigger.0;
+    let Some(definition) = definitions.get(&spell_cast.spell) else {
+        return;
+    };
+
+    let SpellKind::Projectile(projectile) = &definition.kind else {
+        return;
+    };
+
+    let position = origins
+        .get(trigger.target())
+        .map(|origin| origin.translation())
+        .unwrap_or_default();
+
+    commands.spawn(ProjectileVolley {
+        spell_cast: spell_cast.clone(),
+        origin: trigger.target(),
+        position,
+        nb_projectiles_left: projectile.count.get(),
+        projectile_timer: Timer::from_seconds(projectile.interval, TimerMode::Repeating),
+    });
+}
+
+fn update_projectile_volleys(
+    time: Res<Time>,
+    mut commands: Commands,
+    definitions: Res<Assets<SpellDefinition>>,
+    assets: Res<AssetServer>,
+    mut volleys: Query<(Entity, &mut ProjectileVolley)>,
+    origins: Query<&GlobalTransform>,
+    teams: Query<&Team>,
+) {
+    for (volley_entity, mut volley) in &mut volleys {
+        if let Ok(origin) = origins.get(volley.origin) {
+            volley.position = origin.translation();
+        }
+
+        if !volley.projectile_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let Some(definition) = definitions.get(&volley.spell_cast.spell) else {
+            commands.entity(volley_entity).despawn();
+            continue;
+        };
+
+        let SpellKind::Projectile(projectile) = &definition.kind else {
+            commands.entity(volley_entity).despawn();
+            continue;
+        };
+
+        let Some(nb_projectiles_left) = volley.nb_projectiles_left.checked_sub(1) else {
+            commands.entity(volley_entity).despawn();
+            continue;
+        };
+
+        volley.nb_projectiles_left = nb_projectiles_left;
+
+        let fire_direction = volley.spell_cast.direction;
+        let angle = if fire_direction == Vec2::ZERO {
+            rand::random::<f32>() * 2.0 * PI
+        } else {
+            Vec2::X.angle_to(fire_direction) + (rand::random::<f32>() - 0.5) * projectile.spread
+        };
+
+        let projectile_entity = commands
+            .spawn((
+                RigidBody::Dynamic,
+                LinearVelocity(Vec2::from_angle(angle) * projectile.speed),
+                LinearDamping(projectile.damping),
+                Transform::from_translation(volley.position)
+                    .with_rotation(Quat::from_rotation_z(angle)),
+                Projectile {
+                    payload: SpellPayload::new(volley.spell_cast.caster, definition),
+                    pierce_left: match definition.hit_policy {
+                        HitPolicy::Pierce(max_targets) => max_targets.saturating_sub(1),
+                        HitPolicy::Once | HitPolicy::Every(_) => 0,
+                    },
+                    live_timer: Timer::from_seconds(projectile.lifetime, TimerMode::Once),
+                },
+            ))
+            .observe(resolve_projectile_hit)
+            .id();
+
+        if let Some(sprite) = &definition.sprite {
+            commands
+                .entity(projectile_entity)
+                .insert(sprite.sprite(&assets));
+        }
+
+        if let Some(bounce) = projectile.bounce {
+            commands.entity(projectile_entity).insert(Bounce(bounce));
+        }
+
+        if let Some(homing) = projectile.homing {
+            commands.entity(projectile_entity).insert(homing);
+        }
+
+        if let Some(split) = &projectile.split {
+            commands.entity(projectile_entity).insert(split.clone());
+        }
+
+        commands.trigger(SpawnHitboxEvent(HitboxInfo {
+            shape: projectile.shape.clone(),
+            offset: Vec2::ZERO,
+            rotation: 0.0,
+            parent: projectile_entity,
+            team: teams
+                .get(volley.spell_cast.caster)
+                .copied()
+                .unwrap_or_default(),
+            policy: definition.hit_policy,
+            live_timer: None,
+        }));
+
+        if volley.nb_projectiles_left == 0 {
+            commands.entity(volley_entity).despawn();
+        }
+    }
+}
+
+fn update_and_despawn_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectiles: Query<(Entity, &mut Projectile, &Transform)>,
+) {
+    let delta = time.delta();
+
+    for (entity, mut projectile, transform) in &mut projectiles {
+        projectile.live_timer.tick(delta);
+
+        if projectile.live_timer.finished() {
+            projectile
+                .payload
+                .detonate(&mut commands, transform.translation.xy());
+
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn home_projectiles(
+    time: Res<Time>,
+    friendly_fire: Res<FriendlyFire>,
+    mut projectiles: Query<(&Projectile, &Homing, &mut LinearVelocity, &mut Transform)>,
+    targets: Query<&Team, (With<Health>, Without<Respawning>)>,
+    teams: Query<&Team>,
+    spatial_hash: Res<SpatialHash>,
+) {
+    let delta = time.delta_secs();
+
+    for (projectile, homing, mut linear_velocity, mut transform) in &mut projectiles {
+        let team = teams
+            .get(projectile.payload.caster)
+            .copied()
+            .unwrap_or_default();
+        let position = transform.translation.xy();
+        let Ok(heading) = Dir2::new(linear_velocity.0) else {
+            continue;
+        };
+
+        let closest_target = spatial_hash
+            .query_radius(SpatialLayer::LivingEntity, position, homing.range)
+            .filter(|(target, _)| {
+                targets.get(*target).is_ok_and(|target_team| {
+                    *target_team != Team::Neutral && friendly_fire.can_hit(team, *target_team)
+                })
+            })
+            .map(|(_, target_position)| target_position - position)
+            .filter(|to_target| heading.angle_to(*to_target).abs() <= homing.angle)
+            .min_by(|to_target_1, to_target_2| {
+                to_target_1
+                    .length()
+                    .partial_cmp(&to_target_2.length())
+                    .unwrap()
+            });
+
+        let Some(to_target) = closest_target else {
+            continue;
+        };
+
+        let max_turn = homing.turn_rate * delta;
+        let turn = heading.angle_to(to_target).clamp(-max_turn, max_turn);
+
+        linear_velocity.0 = Vec2::from_angle(turn).rotate(linear_velocity.0);
+        transform.rotation = Quat::from_rotation_z(Vec2::X.angle_to(linear_velocity.0));
+    }
+}
+
+fn bounce_projectiles(
+    time: Res<Time>,
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut projectiles: Query<(Entity, &mut Bounce, &mut LinearVelocity, &mut Transform)>,
+    projectiles_info: Query<(&Projectile, Option<&Split>)>,
+    walls: Query<(), With<TiledColliderPolygons>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut bounce, mut linear_velocity, mut transform) in &mut projectiles {
+        let Ok(direction) = Dir2::new(linear_velocity.0) else {
+            continue;
+        };
+
+        let Some(hit) = spatial_query.cast_ray_predicate(
+            transform.translation.xy(),
+            direction,
+            linear_velocity.0.length() * delta,
+            true,
+            &SpatialQueryFilter::default(),
+            &|collider| walls.contains(collider),
+        ) else {
+            continue;
+        };
+
+        if bounce.0 == 0 {
+            if let Ok((projectile, split)) = projectiles_info.get(entity) {
+                projectile
+                    .payload
+                    .detonate(&mut commands, transform.translation.xy());
+
+                if let Some(split) = split {
+                    split_projectile(
+                        &mut commands,
+                        entity,
+                        projectile,
+                        split,
+                        linear_velocity.0.reflect(hit.normal),
+                    );
+                }
+            }
+
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        bounce.0 -= 1;
+
+        linear_velocity.0 = linear_velocity.0.reflect(hit.normal);
+        transform.rotation = Quat::from_rotation_z(Vec2::X.angle_to(linear_velocity.0));
+    }
+}
+
+fn split_projectile(
+    commands: &mut Commands,
+    entity: Entity,
+    projectile: &Projectile,
+    split: &Split,
+    direction: Vec2,
+) {
+    let base_angle = Vec2::X.angle_to(direction);
+
+    for i in 0..split.count {
+        let offset = if split.count > 1 {
+            (i as f32 / (split.count - 1) as f32 - 0.5) * split.spread
+        } else {
+            0.0
+        };
+
+        commands.trigger_targets(
+            CastSpellEvent {
+                spell: split.spell.clone(),
+                caster: Some(projectile.payload.caster),
+                direction: Some(Vec2::from_angle(base_angle + offset)),
+            },
+            entity,
+        );
+    }
+}
+
+fn resolve_projectile_hit(
+    trigger: Trigger<HitEntityEvent>,
+    mut commands: Commands,
+    mut projectiles: Query<(&mut Projectile, &LinearVelocity, &Transform, Option<&Split>)>,
+    entities: Query<(), With<Health>>,
+) {
+    if !entities.contains(trigger.0) {
+        return;
+    }
+
+    let (mut projectile, linear_velocity, transform, split) =
+        projectiles.get_mut(trigger.target()).unwrap();
+
+    let detonated = projectile.pierce_left == 0
+        && projectile
+            .payload
+            .detonate(&mut commands, transform.translation.xy());
+
+    if !detonated {
+        projectile
+            .payload
+            .hit(&mut commands, trigger.0, linear_velocity.0);
+    }
+
+    if let Some(split) = split {
+        split_projectile(
+            &mut commands,
+            trigger.target(),
+            &projectile,
+            split,
+            linear_velocity.0,
+        );
+    }
+
+    if projectile.pierce_left == 0 {
+        commands.entity(trigger.target()).despawn();
+    } else {
+        projectile.pierce_left -= 1;
+    }
+}
@@ -0,0 +1,143 @@
+use std::f32::consts::PI;
+
+use avian2d::prelude::{LinearVelocity, RigidBody};
+use bevy::prelude::*;
+
+use crate::{health::Health, living_entity::Team};
+
+use super::{
+    CastSpellEvent, SpellCast, SpellPayload,
+    definition::{SpellDefinition, SpellKind},
+    hitbox::{HitEntityEvent, HitboxInfo, SpawnHitboxEvent},
+};
+
+pub fn plugin(app: &mut App) {
+    app.add_event::<CastSpawnerSpell>();
+
+    app.add_observer(cast_spawner);
+
+    app.add_systems(Update, update_spawners);
+}
+
+#[derive(Event)]
+pub struct CastSpawnerSpell(pub SpellCast);
+
+#[derive(Component)]
+struct Spawner {
+    payload: SpellPayload,
+    spawn: String,
+    live_timer: Timer,
+    spawn_timer: Timer,
+}
+
+fn cast_spawner(
+    trigger: Trigger<CastSpawnerSpell>,
+    mut commands: Commands,
+    definitions: Res<Assets<SpellDefinition>>,
+    origins: Query<&GlobalTransform>,
+    teams: Query<&Team>,
+    assets: Res<AssetServer>,
+) {
+    let spell_cast = &trigger.0;
+    let Some(definition) = definitions.get(&spell_cast.spell) else {
+        return;
+    };
+
+    let SpellKind::Spawner(spawner) = &definition.kind else {
+        return;
+    };
+
+    let translation = origins
+        .get(trigger.target())
+        .map(|origin| origin.translation())
+        .unwrap_or_default();
+
+    let spawner_entity = commands
+        .spawn((
+            Transform::from_translation(translation),
+            RigidBody::Dynamic,
+            LinearVelocity(spell_cast.direction * spawner.speed),
+            Spawner {
+                payload: SpellPayload::new(spell_cast.caster, definition),
+                spawn: spawner.spawn.clone(),
+                live_timer: Timer::from_seconds(spawner.lifetime, TimerMode::Once),
+                spawn_timer: Timer::from_seconds(spawner.spawn_interval, TimerMode::Repeating),
+            },
+        ))
+        .observe(resolve_enemy_hit)
+        .id();
+
+    if let Some(sprite) = &definition.sprite {
+        commands
+            .entity(spawner_entity)
+            .insert(sprite.sprite(&assets));
+    }
+
+    commands.trigger(SpawnHitboxEvent(HitboxInfo {
+        shape: spawner.shape.clone(),
+        offset: Vec2::ZERO,
+        rotation: 0.0,
+        parent: spawner_entity,
+        team: teams.get(spell_cast.caster).copied().unwrap_or_default(),
+        policy: definition.hit_policy,
+        live_timer: None,
+    }));
+}
+
+fn update_spawners(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut spawners: Query<(Entity, &mut Spawner, &Transform)>,
+) {
+    let delta = time.delta();
+    for (entity, mut spawner, transform) in &mut spawners {
+        spawner.live_timer.tick(delta);
+        spawner.spawn_timer.tick(delta);
+
+        if spawner.spawn_timer.just_finished() {
+            let angle = rand::random::<f32>() * 2.0 * PI;
+
+            commands.trigger_targets(
+                CastSpellEvent {
+                    spell: spawner.spawn.clone(),
+                    caster: Some(spawner.payload.caster),
+                    direction: Some(Vec2::from_angle(angle)),
+                },
+                entity,
+            );
+        }
+
+        if spawner.live_timer.finished() {
+            spawner
+                .payload
+                .detonate(&mut commands, transform.translation.xy());
+
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn resolve_enemy_hit(
+    trigger: Trigger<HitEntityEvent>,
+    mut commands: Commands,
+    spawners: Query<(&Spawner, &LinearVelocity, &Transform)>,
+    entities: Query<(), With<Health>>,
+) {
+    if !entities.contains(trigger.0) {
+        return;
+    }
+
+    let (spawner, linear_velocity, transform) = spawners.get(trigger.target()).unwrap();
+
+    if spawner
+        .payload
+        .detonate(&mut commands, transform.translation.xy())
+    {
+        commands.entity(trigger.target()).despawn();
+        return;
+    }
+
+    spawner
+        .payload
+        .hit(&mut commands, trigger.0, linear_velocity.0);
+}
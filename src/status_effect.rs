@@ -0,0 +1,256 @@
+use bevy::{color::palettes, prelude::*};
+use serde::Deserialize;
+
+use crate::health::{DamageEvent, DamageKind, DamageType, Health};
+
+const STATUS_ICON_SIZE: f32 = 4.0;
+
+pub struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ApplyStatusEffectEvent>();
+
+        app.add_observer(apply_status_effect);
+
+        app.add_systems(Update, update_status_effects);
+        app.add_systems(PostUpdate, update_status_icons);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusEffectKind {
+    Burn,
+    Poison,
+    Slow,
+    Stun,
+}
+
+impl StatusEffectKind {
+    fn damage_type(&self) -> DamageType {
+        match self {
+            StatusEffectKind::Burn => DamageType::Fire,
+            StatusEffectKind::Poison => DamageType::Poison,
+            StatusEffectKind::Slow | StatusEffectKind::Stun => DamageType::Physical,
+        }
+    }
+
+    fn icon_color(&self) -> Color {
+        match self {
+            StatusEffectKind::Burn => palettes::css::ORANGE_RED.into(),
+            StatusEffectKind::Poison => palettes::basic::LIME.into(),
+            StatusEffectKind::Slow => palettes::css::LIGHT_BLUE.into(),
+            StatusEffectKind::Stun => palettes::basic::YELLOW.into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stacking {
+    #[default]
+    Refresh,
+    Stack(u32),
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub duration: f32,
+    #[serde(default)]
+    pub tick_damage: i32,
+    #[serde(default = "default_tick_interval")]
+    pub tick_interval: f32,
+    #[serde(default = "default_speed_multiplier")]
+    pub speed_multiplier: f32,
+    #[serde(default)]
+    pub stacking: Stacking,
+}
+
+fn default_tick_interval() -> f32 {
+    1.0
+}
+
+fn default_speed_multiplier() -> f32 {
+    1.0
+}
+
+#[derive(Event, Clone, Copy)]
+pub struct ApplyStatusEffectEvent {
+    pub source: Entity,
+    pub target: Entity,
+    pub effect: StatusEffect,
+}
+
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    active: Vec<ActiveStatusEffect>,
+}
+
+struct ActiveStatusEffect {
+    effect: StatusEffect,
+    source: Entity,
+    stacks: u32,
+    duration_timer: Timer,
+    tick_timer: Timer,
+}
+
+impl ActiveStatusEffect {
+    fn new(effect: StatusEffect, source: Entity) -> Self {
+        Self {
+            effect,
+            source,
+            stacks: 1,
+            duration_timer: Timer::from_seconds(effect.duration, TimerMode::Once),
+            tick_timer: Timer::from_seconds(effect.tick_interval, TimerMode::Repeating),
+        }
+    }
+}
+
+impl StatusEffects {
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.active.iter().any(|active| active.effect.kind == kind)
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusEffectKind::Stun)
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        self.active
+            .iter()
+            .map(|active| active.effect.speed_multiplier)
+            .fold(1.0, f32::min)
+    }
+
+    pub fn apply(&mut self, effect: StatusEffect, source: Entity) {
+        let Some(active) = self
+            .active
+            .iter_mut()
+            .find(|active| active.effect.kind == effect.kind)
+        else {
+            self.active.push(ActiveStatusEffect::new(effect, source));
+            return;
+        };
+
+        match effect.stacking {
+            Stacking::Refresh => {
+                *active = ActiveStatusEffect::new(effect, source);
+            }
+            Stacking::Stack(max_stacks) => {
+                let stacks = u32::min(active.stacks + 1, max_stacks);
+                *active = ActiveStatusEffect::new(effect, source);
+                active.stacks = stacks;
+            }
+            Stacking::Ignore => {}
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+}
+
+#[derive(Component)]
+struct StatusIcon;
+
+fn apply_status_effect(
+    trigger: Trigger<ApplyStatusEffectEvent>,
+    mut commands: Commands,
+    mut targets: Query<Option<&mut StatusEffects>, With<Health>>,
+) {
+    let event = trigger.event();
+
+    let Ok(status_effects) = targets.get_mut(event.target) else {
+        return;
+    };
+
+    match status_effects {
+        Some(mut status_effects) => status_effects.apply(event.effect, event.source),
+        None => {
+            let mut status_effects = StatusEffects::default();
+            status_effects.apply(event.effect, event.source);
+
+            commands.entity(event.target).insert(status_effects);
+        }
+    }
+}
+
+fn update_status_effects(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut entities: Query<(Entity, &mut StatusEffects)>,
+) {
+    let delta = time.delta();
+
+    for (entity, mut status_effects) in &mut entities {
+        for active in &mut status_effects.bypass_change_detection().active {
+            active.duration_timer.tick(delta);
+
+            if active.effect.tick_damage == 0 {
+                continue;
+            }
+
+            for _ in 0..active.tick_timer.tick(delta).times_finished_this_tick() {
+                commands.trigger(DamageEvent {
+                    source: active.source,
+                    target: entity,
+                    amount: active.effect.tick_damage * active.stacks as i32,
+                    damage_type: active.effect.kind.damage_type(),
+                    knockback: Vec2::ZERO,
+                    stagger: 0.0,
+                    kind: DamageKind::Tick,
+                    on_hit: Vec::new(),
+                });
+            }
+        }
+
+        if status_effects
+            .active
+            .iter()
+            .any(|active| active.duration_timer.finished())
+        {
+            status_effects
+                .active
+                .retain(|active| !active.duration_timer.finished());
+        }
+    }
+}
+
+fn update_status_icons(
+    mut commands: Commands,
+    entities: Query<(Entity, &StatusEffects, Option<&Children>), Changed<StatusEffects>>,
+    icons: Query<(), With<StatusIcon>>,
+) {
+    for (entity, status_effects, children) in &entities {
+        if let Some(children) = children {
+            for child in children {
+                if icons.contains(*child) {
+                    commands.entity(*child).despawn();
+                }
+            }
+        }
+
+        let nb_icons = status_effects.active.len() as f32;
+
+        for (i, active) in status_effects.active.iter().enumerate() {
+            let x = (i as f32 - (nb_icons - 1.0) / 2.0) * (STATUS_ICON_SIZE + 1.0);
+
+            let icon = commands
+                .spawn((
+                    Sprite {
+                        color: active.effect.kind.icon_color(),
+                        custom_size: Some(Vec2::splat(STATUS_ICON_SIZE)),
+                        ..Default::default()
+                    },
+                    Transform::from_xyz(x, 21.0, 1.0),
+                    StatusIcon,
+                ))
+                .id();
+
+            commands.entity(entity).add_child(icon);
+        }
+    }
+}
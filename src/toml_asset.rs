@@ -0,0 +1,60 @@
+use std::{error::Error, marker::PhantomData};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::de::DeserializeOwned;
+
+pub struct TomlAssetPlugin<A> {
+    extensions: &'static [&'static str],
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A> TomlAssetPlugin<A> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A: Asset + DeserializeOwned> Plugin for TomlAssetPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<A>();
+        app.register_asset_loader(TomlAssetLoader::<A> {
+            extensions: self.extensions,
+            marker: PhantomData,
+        });
+    }
+}
+
+struct TomlAssetLoader<A> {
+    extensions: &'static [&'static str],
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset + DeserializeOwned> AssetLoader for TomlAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<A, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let asset = toml::from_str(std::str::from_utf8(&bytes)?)?;
+
+        Ok(asset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
@@ -1,6 +1,8 @@
-use bevy::prelude::*;
+use bevy::{color::palettes, prelude::*};
 
-use crate::{living_entity::player::Player, ui::ui_dialog::UiDialogPlugin, xp::XpInventory};
+use crate::{
+    living_entity::player::Player, mana::Mana, ui::ui_dialog::UiDialogPlugin, xp::XpInventory,
+};
 
 pub mod ui_dialog;
 
@@ -11,14 +13,18 @@ impl Plugin for UiPlugin {
         app.add_plugins(UiDialogPlugin);
 
         app.add_observer(spawn_xp_text);
+        app.add_observer(spawn_mana_bar);
 
-        app.add_systems(Update, update_xp_text);
+        app.add_systems(Update, (update_xp_text, update_mana_bar));
     }
 }
 
 #[derive(Component)]
 struct XpText;
 
+#[derive(Component)]
+struct ManaBarFill;
+
 fn spawn_xp_text(_trigger: Trigger<OnAdd, XpInventory>, mut commands: Commands) {
     commands.spawn((Text::new("Player Xp: 0"), XpText));
 }
@@ -29,3 +35,43 @@ fn update_xp_text(
 ) {
     xp_text.0 = format!("Player Xp: {}", player_xp.amount());
 }
+
+fn spawn_mana_bar(
+    trigger: Trigger<OnAdd, Mana>,
+    mut commands: Commands,
+    players: Query<(), With<Player>>,
+) {
+    if !players.contains(trigger.target()) {
+        return;
+    }
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(28.0),
+            left: Val::Px(4.0),
+            width: Val::Px(200.0),
+            height: Val::Px(10.0),
+            border: UiRect::all(Val::Px(2.0)),
+            ..Default::default()
+        },
+        BorderColor(palettes::basic::BLACK.into()),
+        BackgroundColor(palettes::css::DARK_SLATE_GRAY.into()),
+        children![(
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            BackgroundColor(palettes::basic::BLUE.into()),
+            ManaBarFill,
+        )],
+    ));
+}
+
+fn update_mana_bar(
+    mut mana_bar_fill: Single<&mut Node, With<ManaBarFill>>,
+    player_mana: Single<&Mana, (Changed<Mana>, With<Player>)>,
+) {
+    mana_bar_fill.width = Val::Percent(player_mana.fraction() * 100.0);
+}
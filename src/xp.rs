@@ -1,5 +1,10 @@
 use bevy::{color::palettes, prelude::*};
 
+use crate::{
+    living_entity::player::Respawning,
+    spatial_hash::{SpatialHash, SpatialLayer},
+};
+
 pub struct XpPlugin;
 
 impl Plugin for XpPlugin {
@@ -51,6 +56,13 @@ impl XpInventory {
         false
     }
 
+    pub fn lose(&mut self, amount: i32) -> i32 {
+        let lost = amount.clamp(0, self.amount.max(0));
+        self.amount -= lost;
+
+        lost
+    }
+
     pub fn amount(&self) -> i32 {
         self.amount
     }
@@ -83,23 +95,25 @@ fn spawn_xp(trigger: Trigger<SpawnXpEvent>, mut commands: Commands) {
                 0.0,
             ),
             Xp(xp_amount),
+            SpatialLayer::Pickup,
         ));
     }
 }
 
 fn start_collecting(
     mut commands: Commands,
-    collectors: Query<(Entity, &Transform), With<XpInventory>>,
-    xp: Query<(Entity, &Transform), (With<Xp>, Without<Collecting>)>,
+    spatial_hash: Res<SpatialHash>,
+    collectors: Query<(Entity, &Transform), (With<XpInventory>, Without<Respawning>)>,
+    uncollected_xp: Query<&Transform, (With<Xp>, Without<Collecting>)>,
 ) {
     for (collector, transform) in &collectors {
-        for (xp, xp_transform) in &xp {
-            let dist = transform.translation - xp_transform.translation;
-            let dist = dist.length();
+        let nearby_xp =
+            spatial_hash.query_radius(SpatialLayer::Pickup, transform.translation.xy(), 500.0);
 
-            if dist > 500.0 {
+        for (xp, _) in nearby_xp {
+            let Ok(xp_transform) = uncollected_xp.get(xp) else {
                 continue;
-            }
+            };
 
             commands.entity(xp).insert(Collecting {
                 collected_by: collector,
@@ -113,7 +127,7 @@ fn start_collecting(
 fn update_xp(
     mut commands: Commands,
     time: Res<Time>,
-    collectors: Query<&Transform, With<XpInventory>>,
+    collectors: Query<&Transform, (With<XpInventory>, Without<Respawning>)>,
     mut xp: Query<(Entity, &mut Transform, &mut Collecting), Without<XpInventory>>,
 ) {
     for (xp, mut xp_transform, mut collecting) in &mut xp {